@@ -0,0 +1,127 @@
+//! Command line interface, used instead of the GUI when the tracker is started with a command.
+
+use crate::core::csv_export::{CsvOptions, DurationFormat, ExportKind, GameColumn, SessionColumn, export_csv};
+use crate::core::json_storage::{Game, GameId, load_games};
+use chrono::NaiveDate;
+use std::path::PathBuf;
+
+const USAGE: &str = "Usage:
+  vn_time_tracker export-csv <sessions|games> <output.csv> [options]
+
+Options:
+  --from YYYY-MM-DD      first day to include
+  --to YYYY-MM-DD        last day to include
+  --game <id or name>    only this game
+  --columns a,b,c        columns in order (sessions: game_id, game, start, end, duration, note, progress;
+                         games: id, name, path, playtime, sessions, status, category, tags, added, last_played, notes)
+  --date-format <fmt>    strftime format of dates, default \"%Y-%m-%d %H:%M:%S\"
+  --duration <format>    seconds, hours or hms, default hours";
+
+/// Runs the command in `args` (without the program name).
+/// Returns `None` if no command was given and the GUI should start.
+pub fn run(args: &[String]) -> Option<Result<String, String>> {
+    let (command, rest) = args.split_first()?;
+
+    Some(match command.as_str() {
+        "export-csv" => parse_export_args(rest, &load_games()).and_then(|(path, options)| {
+            let rows = export_csv(&path, &options)?;
+            Ok(format!("Exported {} rows to {}", rows, path.display()))
+        }),
+        "help" | "--help" | "-h" => Ok(USAGE.to_string()),
+        other => Err(format!("Unknown command: {}\n\n{}", other, USAGE)),
+    })
+}
+
+/// Connects stdout and stderr to the console the tracker was started from.
+///
+/// On Windows the tracker is a GUI program, which gets no console of its own, so command output
+/// would go nowhere. Started without a console (e.g. from Explorer) there's nothing to attach to.
+#[cfg(target_os = "windows")]
+pub fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // SAFETY: AttachConsole takes a plain process id and only fails if there's no console to attach to
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn attach_console() {}
+
+/// Finds a game by id or by case-insensitive name.
+fn find_game(games: &[Game], query: &str) -> Result<GameId, String> {
+    games
+        .iter()
+        .find(|g| query.parse().ok() == Some(g.id) || g.name.eq_ignore_ascii_case(query))
+        .map(|g| g.id)
+        .ok_or_else(|| format!("No game matches \"{}\"", query))
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    value.parse().map_err(|_| format!("Invalid date \"{}\", expected YYYY-MM-DD", value))
+}
+
+/// Looks up comma-separated column names among `all` by their `key`.
+fn parse_columns<T: Copy>(value: &str, all: &[T], key: impl Fn(&T) -> &'static str) -> Result<Vec<T>, String> {
+    value
+        .split(',')
+        .map(|name| {
+            let name = name.trim();
+            all.iter().find(|c| key(c) == name).copied().ok_or_else(|| format!("Unknown column: {}", name))
+        })
+        .collect()
+}
+
+/// Parses the arguments of `export-csv` into the output path and export options.
+fn parse_export_args(args: &[String], games: &[Game]) -> Result<(PathBuf, CsvOptions), String> {
+    let mut options = CsvOptions::default();
+    let mut positional = Vec::new();
+    let mut columns = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            positional.push(arg.as_str());
+            continue;
+        }
+
+        let value = args.next().ok_or_else(|| format!("Missing value for {}", arg))?;
+        match arg.as_str() {
+            "--from" => options.from = Some(parse_date(value)?),
+            "--to" => options.to = Some(parse_date(value)?),
+            "--game" => options.game_id = Some(find_game(games, value)?),
+            "--columns" => columns = Some(value.as_str()),
+            "--date-format" => options.date_format = value.clone(),
+            "--duration" => {
+                options.duration_format = DurationFormat::ALL
+                    .into_iter()
+                    .find(|f| f.key() == value)
+                    .ok_or_else(|| format!("Unknown duration format: {}", value))?;
+            }
+            _ => return Err(format!("Unknown option: {}\n\n{}", arg, USAGE)),
+        }
+    }
+
+    let [kind, path] = positional[..] else {
+        return Err(USAGE.to_string());
+    };
+    options.kind = ExportKind::ALL
+        .into_iter()
+        .find(|k| k.key() == kind)
+        .ok_or_else(|| format!("Expected \"sessions\" or \"games\", got \"{}\"", kind))?;
+
+    if let Some(columns) = columns {
+        match options.kind {
+            ExportKind::Sessions => options.session_columns = parse_columns(columns, &SessionColumn::ALL, |c| c.key())?,
+            ExportKind::Games => options.game_columns = parse_columns(columns, &GameColumn::ALL, |c| c.key())?,
+        }
+    }
+
+    Ok((PathBuf::from(path), options))
+}
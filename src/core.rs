@@ -1,4 +1,23 @@
+pub mod backup;
+pub mod category_crud;
+pub mod covers;
+pub mod csv_export;
+pub mod csv_import;
+pub mod duration;
 pub mod game_crud;
+pub mod game_filter;
 pub mod game_launch;
+pub mod goals;
 pub mod icons;
 pub mod json_storage;
+pub mod library_merge;
+pub mod relink;
+pub mod reminders;
+#[cfg(test)]
+pub mod scratch_dir;
+pub mod session_crud;
+pub mod session_overlaps;
+pub mod statistics;
+pub mod sync_storage;
+pub mod tag_crud;
+pub mod year_review;
@@ -0,0 +1,353 @@
+use crate::core::covers::delete_cover_file;
+use crate::core::duration::DATE_FORMAT;
+use crate::core::json_storage::{
+    Category, Game, GameId, GoalId, LimitId, PlayGoal, PlayLimit, Session, load_all_sessions, load_categories,
+    load_games, load_goals, load_limits, save_categories, save_games_and_sessions, save_goals, save_limits,
+};
+use crate::core::library_merge::{ForeignLibrary, apply_library_merge, plan_library_merge};
+use chrono::Local;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Version of the archive layout. Archives from newer versions are refused.
+pub const BACKUP_VERSION: u32 = 1;
+/// Marks an archive as a tracker backup.
+const BACKUP_FORMAT: &str = "vn-time-tracker-backup";
+const MANIFEST_FILE: &str = "manifest.json";
+const SETTINGS_FILE: &str = "settings.json";
+const GAMES_ENTRY: &str = "games.json";
+const SESSIONS_ENTRY: &str = "sessions.json";
+const CATEGORIES_ENTRY: &str = "categories.json";
+const GOALS_ENTRY: &str = "goals.json";
+const LIMITS_ENTRY: &str = "limits.json";
+
+/// Table of contents of a backup archive.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BackupManifest {
+    pub format: String,
+    pub version: u32,
+    /// When the backup was made, in [`DATE_FORMAT`].
+    pub created: String,
+    /// Tracker version that made the backup.
+    pub app_version: String,
+    pub files: Vec<BackupFile>,
+}
+
+/// One file of a backup archive.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BackupFile {
+    pub name: String,
+    pub size: u64,
+    /// Lowercase hex SHA-256 of the content.
+    pub sha256: String,
+}
+
+/// What restoring does with the data already there.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum RestoreMode {
+    /// The backup replaces all current data and settings.
+    #[default]
+    Replace,
+    /// Games, sessions, categories, goals and reminders missing here are added. Settings stay as they are.
+    Merge,
+}
+
+impl RestoreMode {
+    pub const ALL: [RestoreMode; 2] = [RestoreMode::Replace, RestoreMode::Merge];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RestoreMode::Replace => "Replace current data",
+            RestoreMode::Merge => "Merge into current data",
+        }
+    }
+}
+
+/// Checked content of a backup archive.
+pub struct BackupContents {
+    pub manifest: BackupManifest,
+    pub games: Vec<Game>,
+    pub sessions: Vec<Session>,
+    pub categories: Vec<Category>,
+    pub goals: Vec<PlayGoal>,
+    pub limits: Vec<PlayLimit>,
+    /// Serialized preferences, if the backup has them.
+    pub settings: Option<String>,
+    /// Cover thumbnails by path relative to the data directory.
+    pub covers: HashMap<String, Vec<u8>>,
+}
+
+/// Numbers of a finished restore.
+pub struct RestoreSummary {
+    pub games: usize,
+    pub sessions: usize,
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn to_json<T: Serialize>(data: &[T]) -> Result<Vec<u8>, String> {
+    serde_json::to_vec_pretty(data).map_err(|e| e.to_string())
+}
+
+/// Writes all data, the cover cache and `settings` (serialized preferences) into a zip archive at `path`.
+pub fn create_backup(path: &Path, settings: &str) -> Result<BackupManifest, String> {
+    let games = load_games();
+    let mut entries = vec![
+        (GAMES_ENTRY.to_string(), to_json(&games)?),
+        (SESSIONS_ENTRY.to_string(), to_json(&load_all_sessions())?),
+        (CATEGORIES_ENTRY.to_string(), to_json(&load_categories())?),
+        (GOALS_ENTRY.to_string(), to_json(&load_goals())?),
+        (LIMITS_ENTRY.to_string(), to_json(&load_limits())?),
+        (SETTINGS_FILE.to_string(), settings.as_bytes().to_vec()),
+    ];
+    // Covers that went missing from the cache are left out
+    for game in games.iter().filter(|g| !g.cover.is_empty()) {
+        if let Ok(bytes) = fs::read(&game.cover) {
+            entries.push((game.cover.replace('\\', "/"), bytes));
+        }
+    }
+
+    let manifest = BackupManifest {
+        format: BACKUP_FORMAT.to_string(),
+        version: BACKUP_VERSION,
+        created: Local::now().format(DATE_FORMAT).to_string(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        files: entries
+            .iter()
+            .map(|(name, bytes)| BackupFile { name: name.clone(), size: bytes.len() as u64, sha256: sha256_hex(bytes) })
+            .collect(),
+    };
+
+    let write = || -> Result<(), zip::result::ZipError> {
+        let mut zip = ZipWriter::new(File::create(path)?);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip.start_file(MANIFEST_FILE, options)?;
+        zip.write_all(&serde_json::to_vec_pretty(&manifest).unwrap_or_default())?;
+        for (name, bytes) in &entries {
+            zip.start_file(name.as_str(), options)?;
+            zip.write_all(bytes)?;
+        }
+        zip.finish()?;
+        Ok(())
+    };
+    write().map_err(|e| format!("Can't write backup: {}", e))?;
+
+    Ok(manifest)
+}
+
+/// Whether an archive entry name stays inside the data directory.
+fn is_safe_name(name: &str) -> bool {
+    Path::new(name).components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+fn parse_entry<T: DeserializeOwned>(files: &HashMap<String, Vec<u8>>, name: &str) -> Result<Vec<T>, String> {
+    match files.get(name) {
+        Some(bytes) => serde_json::from_slice(bytes).map_err(|e| format!("{} is damaged: {}", name, e)),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Opens a backup archive and checks its format, version and every checksum.
+pub fn read_backup(path: &Path) -> Result<BackupContents, String> {
+    let file = File::open(path).map_err(|e| format!("Can't open backup: {}", e))?;
+    let mut zip = ZipArchive::new(file).map_err(|e| format!("Not a backup archive: {}", e))?;
+
+    let mut read_entry = |name: &str| -> Result<Vec<u8>, String> {
+        let mut entry = zip.by_name(name).map_err(|_| format!("Backup is missing {}", name))?;
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).map_err(|e| format!("Can't read {}: {}", name, e))?;
+        Ok(bytes)
+    };
+
+    let manifest: BackupManifest = serde_json::from_slice(&read_entry(MANIFEST_FILE)?)
+        .map_err(|e| format!("Backup manifest is damaged: {}", e))?;
+    if manifest.format != BACKUP_FORMAT {
+        return Err("Not a VN Time Tracker backup".to_string());
+    }
+    if manifest.version > BACKUP_VERSION {
+        return Err(format!(
+            "Backup version {} needs a newer tracker (this one reads up to {})",
+            manifest.version, BACKUP_VERSION
+        ));
+    }
+
+    let mut files = HashMap::new();
+    for listed in &manifest.files {
+        if !is_safe_name(&listed.name) {
+            return Err(format!("Backup contains an unsafe path: {}", listed.name));
+        }
+        let bytes = read_entry(&listed.name)?;
+        if bytes.len() as u64 != listed.size || sha256_hex(&bytes) != listed.sha256 {
+            return Err(format!("Checksum mismatch in {}, the backup is damaged", listed.name));
+        }
+        files.insert(listed.name.clone(), bytes);
+    }
+    if !files.contains_key(GAMES_ENTRY) || !files.contains_key(SESSIONS_ENTRY) {
+        return Err("Backup has no games or sessions".to_string());
+    }
+
+    Ok(BackupContents {
+        games: parse_entry(&files, GAMES_ENTRY)?,
+        sessions: parse_entry(&files, SESSIONS_ENTRY)?,
+        categories: parse_entry(&files, CATEGORIES_ENTRY)?,
+        goals: parse_entry(&files, GOALS_ENTRY)?,
+        limits: parse_entry(&files, LIMITS_ENTRY)?,
+        settings: files.get(SETTINGS_FILE).map(|bytes| String::from_utf8_lossy(bytes).into_owned()),
+        covers: files.into_iter().filter(|(name, _)| name.starts_with("covers/")).collect(),
+        manifest,
+    })
+}
+
+/// Writes a cover from the backup into `data_dir`. Takes another name, like `cover-1.png`,
+/// if a different file is already there. Returns the name relative to `data_dir`.
+fn write_cover(data_dir: &Path, name: &str, bytes: &[u8]) -> Result<String, String> {
+    let original = Path::new(name);
+    let stem = original.file_stem().unwrap_or_default().to_string_lossy();
+    let mut target = name.to_string();
+    let mut n = 1;
+    while let Ok(existing) = fs::read(data_dir.join(&target)) {
+        if existing == bytes {
+            return Ok(target);
+        }
+        let file_name = match original.extension() {
+            Some(ext) => format!("{}-{}.{}", stem, n, ext.to_string_lossy()),
+            None => format!("{}-{}", stem, n),
+        };
+        target = original.with_file_name(file_name).to_string_lossy().into_owned();
+        n += 1;
+    }
+
+    let path = data_dir.join(&target);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Can't create {}: {}", dir.display(), e))?;
+    }
+    fs::write(&path, bytes).map_err(|e| format!("Can't write {}: {}", target, e))?;
+    Ok(target)
+}
+
+/// Writes the backed up cover of a game into `data_dir` and returns its new path,
+/// empty if the backup doesn't have it.
+fn restore_cover(data_dir: &Path, contents: &BackupContents, cover: &str) -> Result<String, String> {
+    let name = cover.replace('\\', "/");
+    match contents.covers.get(&name) {
+        Some(bytes) => write_cover(data_dir, &name, bytes),
+        None => Ok(String::new()),
+    }
+}
+
+/// Replaces all data with the backup. Covers of the current games are removed.
+fn restore_replace(contents: &BackupContents) -> Result<RestoreSummary, String> {
+    let previous_games = load_games();
+    let mut games = contents.games.clone();
+    for game in games.iter_mut().filter(|g| !g.cover.is_empty()) {
+        game.cover = restore_cover(Path::new(""), contents, &game.cover)?;
+    }
+
+    save_games_and_sessions(&games, &contents.sessions)?;
+    save_categories(&contents.categories);
+    save_goals(&contents.goals);
+    save_limits(&contents.limits);
+
+    let kept: HashSet<&str> = games.iter().map(|g| g.cover.as_str()).collect();
+    for game in previous_games.iter().filter(|g| !kept.contains(g.cover.as_str())) {
+        delete_cover_file(&game.cover);
+    }
+
+    Ok(RestoreSummary { games: games.len(), sessions: contents.sessions.len() })
+}
+
+/// Adds what the backup has and the current data doesn't. Games are matched by id, path, then name;
+/// sessions by id or by game and end time.
+fn restore_merge(contents: &BackupContents) -> Result<RestoreSummary, String> {
+    let library = ForeignLibrary {
+        source: String::new(),
+        games: contents.games.clone(),
+        sessions: contents.sessions.clone(),
+        categories: contents.categories.clone(),
+        covers: contents.covers.clone(),
+    };
+    let summary = apply_library_merge(&library, &plan_library_merge(&load_games(), &library))?;
+
+    let mut goals = load_goals();
+    let mut limits = load_limits();
+    let remap = |game_id: Option<GameId>| match game_id {
+        Some(id) => summary.game_ids.get(&id).copied().map(Some),
+        None => Some(None),
+    };
+    for goal in &contents.goals {
+        let Some(game_id) = remap(goal.game_id) else { continue };
+        if !goals.iter().any(|g| g.game_id == game_id && g.period == goal.period && g.minutes == goal.minutes) {
+            goals.push(PlayGoal { id: GoalId::random(), game_id, ..goal.clone() });
+        }
+    }
+    for limit in &contents.limits {
+        let Some(game_id) = remap(limit.game_id) else { continue };
+        if !limits.iter().any(|l| l.game_id == game_id && l.kind == limit.kind && l.minutes == limit.minutes) {
+            limits.push(PlayLimit { id: LimitId::random(), game_id, ..limit.clone() });
+        }
+    }
+    save_goals(&goals);
+    save_limits(&limits);
+
+    Ok(RestoreSummary { games: summary.games_added, sessions: summary.sessions_added })
+}
+
+/// Restores checked backup contents. Settings are applied by the caller, only when replacing.
+pub fn restore_backup(contents: &BackupContents, mode: RestoreMode) -> Result<RestoreSummary, String> {
+    match mode {
+        RestoreMode::Replace => restore_replace(contents),
+        RestoreMode::Merge => restore_merge(contents),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::scratch_dir::ScratchDir;
+
+    fn contents(covers: &[(&str, &[u8])]) -> BackupContents {
+        BackupContents {
+            manifest: BackupManifest {
+                format: BACKUP_FORMAT.to_string(),
+                version: 1,
+                created: String::new(),
+                app_version: String::new(),
+                files: Vec::new(),
+            },
+            games: Vec::new(),
+            sessions: Vec::new(),
+            categories: Vec::new(),
+            goals: Vec::new(),
+            limits: Vec::new(),
+            settings: None,
+            covers: covers.iter().map(|(name, bytes)| (name.to_string(), bytes.to_vec())).collect(),
+        }
+    }
+
+    #[test]
+    fn restored_covers_get_a_free_name_keeping_the_extension() {
+        let dir = ScratchDir::new();
+        fs::create_dir_all(dir.0.join("covers")).unwrap();
+        fs::write(dir.0.join("covers/saya.png"), b"current").unwrap();
+        fs::write(dir.0.join("covers/saya-1.png"), b"also current").unwrap();
+        let backup = contents(&[("covers/saya.png", b"backed up"), ("covers/same.png", b"same")]);
+        fs::write(dir.0.join("covers/same.png"), b"same").unwrap();
+
+        assert_eq!(restore_cover(&dir.0, &backup, "covers/saya.png").unwrap(), "covers/saya-2.png");
+        assert_eq!(fs::read(dir.0.join("covers/saya-2.png")).unwrap(), b"backed up");
+        assert_eq!(fs::read(dir.0.join("covers/saya.png")).unwrap(), b"current");
+        // Restoring again finds the file written before
+        assert_eq!(restore_cover(&dir.0, &backup, "covers\\saya.png").unwrap(), "covers/saya-2.png");
+        assert_eq!(restore_cover(&dir.0, &backup, "covers/same.png").unwrap(), "covers/same.png");
+        assert_eq!(restore_cover(&dir.0, &backup, "covers/gone.png").unwrap(), "");
+    }
+}
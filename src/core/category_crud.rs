@@ -0,0 +1,49 @@
+use crate::core::json_storage::{
+    Category, CategoryId, GameId, load_categories, load_games, save_categories, save_games,
+};
+
+/// Adds a new category and returns its id.
+pub fn add_category(name: String) -> CategoryId {
+    let mut categories = load_categories();
+
+    let new_id = CategoryId::random();
+    categories.push(Category { id: new_id, name });
+    save_categories(&categories);
+
+    new_id
+}
+
+/// Rename category
+pub fn rename_category(category_id: CategoryId, new_name: String) {
+    let mut categories = load_categories();
+
+    if let Some(category) = categories.iter_mut().find(|c| c.id == category_id) {
+        category.name = new_name;
+    }
+
+    save_categories(&categories);
+}
+
+/// Removes a category. Its games become uncategorized.
+pub fn delete_category(category_id: CategoryId) {
+    let mut categories = load_categories();
+    categories.retain(|c| c.id != category_id);
+    save_categories(&categories);
+
+    let mut games = load_games();
+    for game in games.iter_mut().filter(|g| g.category_id == Some(category_id)) {
+        game.category_id = None;
+    }
+    save_games(&games);
+}
+
+/// Moves a game into a category, or out of any category with `None`.
+pub fn assign_category(game_id: GameId, category_id: Option<CategoryId>) {
+    let mut games = load_games();
+
+    if let Some(game) = games.iter_mut().find(|g| g.id == game_id) {
+        game.category_id = category_id;
+    }
+
+    save_games(&games);
+}
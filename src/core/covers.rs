@@ -0,0 +1,94 @@
+use crate::core::json_storage::{GameId, load_games, save_games};
+use chrono::Local;
+use image::DynamicImage;
+use pelite::PeFile;
+use std::fs;
+use std::path::Path;
+
+/// Folder next to the data files where cover thumbnails are cached.
+const COVERS_DIR: &str = "covers";
+/// Longest side of a cached thumbnail in pixels.
+const THUMBNAIL_SIZE: u32 = 256;
+
+/// Sets the cover of a game from an image file (png, jpg, ico, ...).
+pub fn set_cover_from_file(game_id: GameId, path: &Path) -> Result<(), String> {
+    let image = image::open(path).map_err(|e| format!("Can't read image: {}", e))?;
+    save_cover(game_id, image)
+}
+
+/// Sets the cover of a game from the largest icon embedded in its executable.
+pub fn set_cover_from_executable(game_id: GameId) -> Result<(), String> {
+    let games = load_games();
+    let game = games.iter().find(|g| g.id == game_id).ok_or("Game not found")?;
+
+    let bytes = fs::read(&game.path).map_err(|e| format!("Can't read executable: {}", e))?;
+    let image = extract_icon(&bytes)?;
+    save_cover(game_id, image)
+}
+
+/// Removes the cover of a game together with its cached thumbnail.
+pub fn remove_cover(game_id: GameId) {
+    let mut games = load_games();
+
+    if let Some(game) = games.iter_mut().find(|g| g.id == game_id) {
+        delete_cover_file(&game.cover);
+        game.cover.clear();
+    }
+
+    save_games(&games);
+}
+
+/// Deletes a cached thumbnail, ignoring covers that were never set.
+pub fn delete_cover_file(cover: &str) {
+    if !cover.is_empty() {
+        let _ = fs::remove_file(cover);
+    }
+}
+
+/// Caches image bytes from another library as the cover of `game_id` and returns the new thumbnail path.
+/// The game itself is not changed.
+pub fn import_cover(game_id: GameId, bytes: &[u8]) -> Result<String, String> {
+    let image = image::load_from_memory(bytes).map_err(|e| format!("Can't read image: {}", e))?;
+    write_thumbnail(game_id, image)
+}
+
+/// Loads a cached thumbnail as RGBA pixels.
+pub fn load_cover(cover: &str) -> Option<image::RgbaImage> {
+    if cover.is_empty() {
+        return None;
+    }
+    image::open(cover).ok().map(|image| image.into_rgba8())
+}
+
+/// Decodes the first icon group of a PE executable. The ICO decoder picks its largest entry.
+fn extract_icon(bytes: &[u8]) -> Result<DynamicImage, String> {
+    let pe = PeFile::from_bytes(bytes).map_err(|e| format!("Not a Windows executable: {}", e))?;
+    let resources = pe.resources().map_err(|_| "Executable has no resources".to_string())?;
+    let (_, group) = resources.icons().filter_map(Result::ok).next().ok_or("Executable has no icon")?;
+
+    let mut ico = Vec::new();
+    group.write(&mut ico).map_err(|e| format!("Can't read icon: {}", e))?;
+    image::load_from_memory(&ico).map_err(|e| format!("Can't decode icon: {}", e))
+}
+
+/// Resizes the image into the cover cache and points the game at it.
+///
+/// The file name carries a timestamp so a changed cover never hits a stale texture.
+fn save_cover(game_id: GameId, image: DynamicImage) -> Result<(), String> {
+    let mut games = load_games();
+    let game = games.iter_mut().find(|g| g.id == game_id).ok_or("Game not found")?;
+
+    let file = write_thumbnail(game_id, image)?;
+    delete_cover_file(&game.cover);
+    game.cover = file;
+    save_games(&games);
+    Ok(())
+}
+
+/// Writes a thumbnail of `image` into the covers folder and returns its path.
+fn write_thumbnail(game_id: GameId, image: DynamicImage) -> Result<String, String> {
+    fs::create_dir_all(COVERS_DIR).map_err(|e| format!("Can't create covers folder: {}", e))?;
+    let file = format!("{}/{}-{}.png", COVERS_DIR, game_id, Local::now().format("%Y%m%d%H%M%S%3f"));
+    image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).save(&file).map_err(|e| format!("Can't save cover: {}", e))?;
+    Ok(file)
+}
@@ -0,0 +1,374 @@
+use crate::core::duration::{parse_date_time, parse_duration};
+use crate::core::json_storage::{Category, Game, GameId, Session, load_all_sessions, load_categories, load_games};
+use crate::core::statistics::DateRange;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Date format used when none is configured.
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// What a CSV export holds, one row per item.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportKind {
+    #[default]
+    Sessions,
+    Games,
+}
+
+impl ExportKind {
+    pub const ALL: [ExportKind; 2] = [ExportKind::Sessions, ExportKind::Games];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportKind::Sessions => "Sessions",
+            ExportKind::Games => "Games",
+        }
+    }
+
+    /// Name used on the command line.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ExportKind::Sessions => "sessions",
+            ExportKind::Games => "games",
+        }
+    }
+}
+
+/// How durations are written.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DurationFormat {
+    /// Whole seconds, e.g. `5400`.
+    Seconds,
+    /// Hours with two decimals, e.g. `1.50`.
+    #[default]
+    DecimalHours,
+    /// `H:MM:SS`, e.g. `1:30:00`.
+    Clock,
+}
+
+impl DurationFormat {
+    pub const ALL: [DurationFormat; 3] = [DurationFormat::Seconds, DurationFormat::DecimalHours, DurationFormat::Clock];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DurationFormat::Seconds => "Seconds",
+            DurationFormat::DecimalHours => "Decimal hours",
+            DurationFormat::Clock => "H:MM:SS",
+        }
+    }
+
+    /// Name used on the command line.
+    pub fn key(&self) -> &'static str {
+        match self {
+            DurationFormat::Seconds => "seconds",
+            DurationFormat::DecimalHours => "hours",
+            DurationFormat::Clock => "hms",
+        }
+    }
+
+    pub fn format(&self, secs: u64) -> String {
+        match self {
+            DurationFormat::Seconds => secs.to_string(),
+            DurationFormat::DecimalHours => format!("{:.2}", secs as f64 / 3600.0),
+            DurationFormat::Clock => format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60),
+        }
+    }
+}
+
+/// Column of a sessions export.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SessionColumn {
+    GameId,
+    Game,
+    Start,
+    End,
+    Duration,
+    Note,
+    Progress,
+}
+
+impl SessionColumn {
+    pub const ALL: [SessionColumn; 7] = [
+        SessionColumn::GameId,
+        SessionColumn::Game,
+        SessionColumn::Start,
+        SessionColumn::End,
+        SessionColumn::Duration,
+        SessionColumn::Note,
+        SessionColumn::Progress,
+    ];
+
+    /// Header text, also the name used on the command line.
+    pub fn key(&self) -> &'static str {
+        match self {
+            SessionColumn::GameId => "game_id",
+            SessionColumn::Game => "game",
+            SessionColumn::Start => "start",
+            SessionColumn::End => "end",
+            SessionColumn::Duration => "duration",
+            SessionColumn::Note => "note",
+            SessionColumn::Progress => "progress",
+        }
+    }
+}
+
+/// Column of a games export.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum GameColumn {
+    Id,
+    Name,
+    Path,
+    Playtime,
+    Sessions,
+    Status,
+    Category,
+    Tags,
+    Added,
+    LastPlayed,
+    Notes,
+}
+
+impl GameColumn {
+    pub const ALL: [GameColumn; 11] = [
+        GameColumn::Id,
+        GameColumn::Name,
+        GameColumn::Path,
+        GameColumn::Playtime,
+        GameColumn::Sessions,
+        GameColumn::Status,
+        GameColumn::Category,
+        GameColumn::Tags,
+        GameColumn::Added,
+        GameColumn::LastPlayed,
+        GameColumn::Notes,
+    ];
+
+    /// Header text, also the name used on the command line.
+    pub fn key(&self) -> &'static str {
+        match self {
+            GameColumn::Id => "id",
+            GameColumn::Name => "name",
+            GameColumn::Path => "path",
+            GameColumn::Playtime => "playtime",
+            GameColumn::Sessions => "sessions",
+            GameColumn::Status => "status",
+            GameColumn::Category => "category",
+            GameColumn::Tags => "tags",
+            GameColumn::Added => "added",
+            GameColumn::LastPlayed => "last_played",
+            GameColumn::Notes => "notes",
+        }
+    }
+}
+
+/// Everything that shapes a CSV export.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CsvOptions {
+    pub kind: ExportKind,
+    /// Columns of a sessions export, in order.
+    pub session_columns: Vec<SessionColumn>,
+    /// Columns of a games export, in order.
+    pub game_columns: Vec<GameColumn>,
+    /// `strftime` format of dates, e.g. `"%d.%m.%Y %H:%M"`.
+    pub date_format: String,
+    pub duration_format: DurationFormat,
+    /// First day to include, `None` for the first session.
+    #[serde(skip)]
+    pub from: Option<NaiveDate>,
+    /// Last day to include, `None` for today.
+    #[serde(skip)]
+    pub to: Option<NaiveDate>,
+    /// Only this game, `None` for every game.
+    pub game_id: Option<GameId>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            kind: ExportKind::default(),
+            session_columns: SessionColumn::ALL.to_vec(),
+            game_columns: GameColumn::ALL.to_vec(),
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            duration_format: DurationFormat::default(),
+            from: None,
+            to: None,
+            game_id: None,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// The selected days, or `None` if the export isn't limited to a date range.
+    fn range(&self) -> Option<DateRange> {
+        if self.from.is_none() && self.to.is_none() {
+            return None;
+        }
+        Some(DateRange::days(self.from.unwrap_or(NaiveDate::MIN), self.to.unwrap_or(NaiveDate::MAX)))
+    }
+}
+
+/// Checks a `strftime` format, since formatting with an invalid one panics.
+///
+/// Dates are stored without a time zone, so zone items like `%z` are refused as well.
+pub fn validate_date_format(format: &str) -> Result<(), String> {
+    let sample = NaiveDate::from_ymd_opt(2000, 1, 1).and_then(|d| d.and_hms_opt(0, 0, 0)).unwrap_or_default();
+    let mut formatted = String::new();
+    write!(formatted, "{}", sample.format(format)).map_err(|_| format!("Invalid date format: {}", format))
+}
+
+/// Rewrites a stored date in `format`. Dates that can't be parsed are kept as they are.
+fn format_date(date: &str, format: &str) -> String {
+    parse_date_time(date).map_or(date.to_string(), |d| d.format(format).to_string())
+}
+
+/// Quotes a field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Appends one CSV line.
+fn push_row(csv: &mut String, fields: impl IntoIterator<Item = String>) {
+    let fields: Vec<String> = fields.into_iter().map(|f| csv_field(&f)).collect();
+    let _ = writeln!(csv, "{}", fields.join(","));
+}
+
+/// Sessions selected by the game and date filters. Sessions partly inside the range are included.
+fn filtered_sessions<'a>(sessions: &'a [Session], options: &CsvOptions) -> Vec<&'a Session> {
+    let range = options.range();
+
+    sessions
+        .iter()
+        .filter(|s| options.game_id.is_none_or(|id| id == s.game_id))
+        .filter(|s| match (&range, s.start_time(), s.end_time()) {
+            (None, _, _) => true,
+            (Some(range), Some(start), Some(end)) => {
+                range.overlap_secs(start, end) > 0 || (range.start <= end && end < range.end)
+            }
+            _ => false,
+        })
+        .collect()
+}
+
+/// One row per session, oldest first. Returns the CSV text and the number of rows.
+pub fn sessions_to_csv(games: &[Game], sessions: &[Session], options: &CsvOptions) -> (String, usize) {
+    let mut rows = filtered_sessions(sessions, options);
+    rows.sort_by_key(|s| s.end_time());
+    let count = rows.len();
+
+    let mut csv = String::new();
+    push_row(&mut csv, options.session_columns.iter().map(|c| c.key().to_string()));
+
+    for session in rows {
+        push_row(
+            &mut csv,
+            options.session_columns.iter().map(|column| match column {
+                SessionColumn::GameId => session.game_id.to_string(),
+                SessionColumn::Game => {
+                    games.iter().find(|g| g.id == session.game_id).map(|g| g.name.clone()).unwrap_or_default()
+                }
+                SessionColumn::Start => {
+                    session.start_time().map(|t| t.format(&options.date_format).to_string()).unwrap_or_default()
+                }
+                SessionColumn::End => format_date(&session.date, &options.date_format),
+                SessionColumn::Duration => options.duration_format.format(session.duration_secs()),
+                SessionColumn::Note => session.note.clone(),
+                SessionColumn::Progress => session.progress.clone(),
+            }),
+        );
+    }
+
+    (csv, count)
+}
+
+/// One row per game, in library order. With a date range, playtime and session count
+/// only cover the range and games not played in it are left out. Returns the CSV text and the number of rows.
+pub fn games_to_csv(
+    games: &[Game],
+    categories: &[Category],
+    sessions: &[Session],
+    options: &CsvOptions,
+) -> (String, usize) {
+    let range = options.range();
+    let sessions = filtered_sessions(sessions, options);
+    let mut count = 0;
+
+    let mut csv = String::new();
+    push_row(&mut csv, options.game_columns.iter().map(|c| c.key().to_string()));
+
+    for game in games.iter().filter(|g| options.game_id.is_none_or(|id| id == g.id)) {
+        let played: Vec<&Session> = sessions.iter().copied().filter(|s| s.game_id == game.id).collect();
+        let playtime = match &range {
+            Some(range) => played.iter().filter_map(|s| Some(range.overlap_secs(s.start_time()?, s.end_time()?))).sum(),
+            None => parse_duration(&game.hours),
+        };
+        if range.is_some() && played.is_empty() {
+            continue;
+        }
+        count += 1;
+
+        push_row(
+            &mut csv,
+            options.game_columns.iter().map(|column| match column {
+                GameColumn::Id => game.id.to_string(),
+                GameColumn::Name => game.name.clone(),
+                GameColumn::Path => game.path.clone(),
+                GameColumn::Playtime => options.duration_format.format(playtime),
+                GameColumn::Sessions => played.len().to_string(),
+                GameColumn::Status => game.status.label().to_string(),
+                GameColumn::Category => game
+                    .category_id
+                    .and_then(|id| categories.iter().find(|c| c.id == id))
+                    .map(|c| c.name.clone())
+                    .unwrap_or_default(),
+                GameColumn::Tags => game.tags.join("; "),
+                GameColumn::Added => format_date(&game.added, &options.date_format),
+                GameColumn::LastPlayed => format_date(&game.last_played, &options.date_format),
+                GameColumn::Notes => game.notes.clone(),
+            }),
+        );
+    }
+
+    (csv, count)
+}
+
+/// Writes the stored games or sessions to `path`. Returns the number of exported rows.
+pub fn export_csv(path: &Path, options: &CsvOptions) -> Result<usize, String> {
+    validate_date_format(&options.date_format)?;
+
+    let games = load_games();
+    let sessions = load_all_sessions();
+    let (csv, count) = match options.kind {
+        ExportKind::Sessions => sessions_to_csv(&games, &sessions, options),
+        ExportKind::Games => games_to_csv(&games, &load_categories(), &sessions, options),
+    };
+
+    fs::write(path, &csv).map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_formats_without_time_zone_are_accepted() {
+        assert!(validate_date_format("%Y-%m-%d %H:%M:%S").is_ok());
+        assert!(validate_date_format("%d.%m.%Y %I:%M %p").is_ok());
+        assert!(validate_date_format("").is_ok());
+    }
+
+    #[test]
+    fn invalid_and_time_zone_formats_are_refused() {
+        for format in ["%Y-%m-%d %z", "%:z", "%Z", "%+", "%Q", "%Y-%"] {
+            assert!(validate_date_format(format).is_err(), "{} should be refused", format);
+        }
+    }
+}
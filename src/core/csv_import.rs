@@ -0,0 +1,464 @@
+use crate::core::duration::{DATE_FORMAT, format_date_time, format_duration, parse_duration};
+use crate::core::json_storage::{
+    Game, GameId, LaunchSettings, PlayStatus, Session, SessionId, load_all_sessions, load_games,
+    save_games_and_sessions,
+};
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Date formats tried when no format is given.
+const DATE_FORMATS: [&str; 10] = [
+    DATE_FORMAT,
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y/%m/%d %H:%M:%S",
+    "%Y/%m/%d %H:%M",
+    "%d.%m.%Y %H:%M:%S",
+    "%d.%m.%Y %H:%M",
+    "%m/%d/%Y %H:%M:%S",
+    "%m/%d/%Y %H:%M",
+];
+
+/// Longest session an import accepts. Longer ones are typos or durations in another unit.
+const MAX_SESSION_SECS: u64 = 3 * 24 * 3600;
+
+/// Imported sessions closer than this to an existing one count as the same session.
+const DUPLICATE_TOLERANCE_SECS: i64 = 60;
+
+/// What a CSV column holds.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportField {
+    #[default]
+    Ignore,
+    Game,
+    Start,
+    End,
+    Duration,
+    Note,
+    Progress,
+}
+
+impl ImportField {
+    pub const ALL: [ImportField; 7] = [
+        ImportField::Ignore,
+        ImportField::Game,
+        ImportField::Start,
+        ImportField::End,
+        ImportField::Duration,
+        ImportField::Note,
+        ImportField::Progress,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImportField::Ignore => "Ignore",
+            ImportField::Game => "Game name",
+            ImportField::Start => "Start time",
+            ImportField::End => "End time",
+            ImportField::Duration => "Duration",
+            ImportField::Note => "Note",
+            ImportField::Progress => "Progress",
+        }
+    }
+
+    /// Guesses the field from a header cell, e.g. `"Game"` or `"ended_at"`.
+    fn from_header(header: &str) -> Self {
+        let header = header.trim().to_lowercase();
+        let has = |words: &[&str]| words.iter().any(|w| header.contains(w));
+
+        if has(&["game", "title", "name"]) && !header.contains("id") {
+            ImportField::Game
+        } else if has(&["start", "begin"]) {
+            ImportField::Start
+        } else if has(&["end", "stop", "finish"]) || header == "date" {
+            ImportField::End
+        } else if has(&["duration", "length", "playtime", "hours", "time played"]) {
+            ImportField::Duration
+        } else if has(&["note", "comment"]) {
+            ImportField::Note
+        } else if has(&["progress", "chapter", "route"]) {
+            ImportField::Progress
+        } else {
+            ImportField::Ignore
+        }
+    }
+}
+
+/// How the rows of a CSV file become sessions.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ImportOptions {
+    /// The first row holds column names and is skipped.
+    pub has_header: bool,
+    /// Field of each column, by column index.
+    pub mapping: Vec<ImportField>,
+    /// `strftime` format of the dates, empty to recognise common formats.
+    pub date_format: String,
+    /// Plain numbers in the duration column are hours instead of seconds.
+    pub numbers_in_hours: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self { has_header: true, mapping: Vec::new(), date_format: String::new(), numbers_in_hours: true }
+    }
+}
+
+/// Picks the separator that splits the first line into the most fields.
+fn detect_delimiter(text: &str) -> char {
+    let first_line = text.lines().next().unwrap_or_default();
+    [',', ';', '\t'].into_iter().max_by_key(|d| first_line.matches(*d).count()).unwrap_or(',')
+}
+
+/// Splits CSV text into rows of fields. Handles quoted fields with separators, doubled quotes and line breaks.
+/// Comma, semicolon and tab separated files are recognised.
+pub fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let delimiter = detect_delimiter(text);
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows.retain(|r| r.iter().any(|f| !f.trim().is_empty()));
+    rows
+}
+
+/// Mapping guessed from the header row, or all columns ignored if there is none.
+pub fn guess_mapping(rows: &[Vec<String>], has_header: bool) -> Vec<ImportField> {
+    let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    match rows.first().filter(|_| has_header) {
+        Some(header) => {
+            (0..columns).map(|i| header.get(i).map_or(ImportField::Ignore, |h| ImportField::from_header(h))).collect()
+        }
+        None => vec![ImportField::Ignore; columns],
+    }
+}
+
+/// Parses a date in `format`, or in one of the common formats if `format` is empty.
+/// A date without time means midnight.
+pub fn parse_import_date(value: &str, format: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    if !format.trim().is_empty() {
+        return NaiveDateTime::parse_from_str(value, format)
+            .ok()
+            .or_else(|| NaiveDate::parse_from_str(value, format).ok().map(|d| d.and_time(Default::default())));
+    }
+
+    DATE_FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(value, f).ok())
+        .or_else(|| value.parse::<NaiveDate>().ok().map(|d| d.and_time(Default::default())))
+}
+
+/// Parses `"1h 30m 0s"`, `"1:30:00"`, `"1:30"` or a plain number of seconds or hours.
+pub fn parse_import_duration(value: &str, numbers_in_hours: bool) -> Option<u64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    if value.contains(':') {
+        let parts: Vec<u64> = value.split(':').map(|p| p.trim().parse().ok()).collect::<Option<_>>()?;
+        let (h, m, s) = match parts[..] {
+            [h, m, s] => (h, m, s),
+            [h, m] => (h, m, 0),
+            _ => return None,
+        };
+        return h.checked_mul(3600)?.checked_add(m.checked_mul(60)?)?.checked_add(s);
+    }
+    if value.ends_with(['h', 'm', 's']) {
+        return Some(parse_duration(value)).filter(|secs| *secs > 0 || value.starts_with('0'));
+    }
+
+    let number: f64 = value.replace(',', ".").parse().ok().filter(|n: &f64| n.is_finite() && *n >= 0.0)?;
+    let secs = if numbers_in_hours { (number * 3600.0).round() } else { number.round() };
+    // `as` would quietly clamp larger values to `u64::MAX`
+    (secs < u64::MAX as f64).then_some(secs as u64)
+}
+
+/// A CSV row turned into a session.
+#[derive(Clone)]
+pub struct PlannedSession {
+    /// Row in the file, `1` for the first one.
+    pub row: usize,
+    pub game_name: String,
+    /// Matching library game, `None` if the game will be created.
+    pub game_id: Option<GameId>,
+    pub end: NaiveDateTime,
+    pub secs: u64,
+    pub note: String,
+    pub progress: String,
+    /// Already stored, or repeated earlier in the file. Duplicates are skipped.
+    pub duplicate: bool,
+}
+
+/// Everything an import would change, shown before anything is written.
+#[derive(Clone, Default)]
+pub struct ImportPlan {
+    pub sessions: Vec<PlannedSession>,
+    /// Rows that can't be imported, with the reason.
+    pub errors: Vec<(usize, String)>,
+    /// Names of games that don't exist yet.
+    pub new_games: Vec<String>,
+}
+
+impl ImportPlan {
+    /// Sessions that will be written.
+    pub fn importable(&self) -> impl Iterator<Item = &PlannedSession> {
+        self.sessions.iter().filter(|s| !s.duplicate)
+    }
+
+    pub fn duplicate_count(&self) -> usize {
+        self.sessions.iter().filter(|s| s.duplicate).count()
+    }
+}
+
+/// Game with the same name, ignoring case and surrounding spaces.
+fn find_game_by_name<'a>(games: &'a [Game], name: &str) -> Option<&'a Game> {
+    let name = name.trim().to_lowercase();
+    games.iter().find(|g| g.name.trim().to_lowercase() == name)
+}
+
+/// Reads one row with the mapping. Needs a game name and two of start, end and duration.
+fn read_row(
+    fields: &[String],
+    options: &ImportOptions,
+) -> Result<(String, NaiveDateTime, u64, String, String), String> {
+    let value = |field: ImportField| {
+        options
+            .mapping
+            .iter()
+            .position(|f| *f == field)
+            .and_then(|i| fields.get(i))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    };
+    let date = |field: ImportField| {
+        value(field)
+            .map(|v| parse_import_date(v, &options.date_format).ok_or_else(|| format!("Can't read date \"{}\"", v)))
+            .transpose()
+    };
+
+    let game_name = value(ImportField::Game).ok_or("Game name is missing")?.to_string();
+    let start = date(ImportField::Start)?;
+    let end = date(ImportField::End)?;
+    let secs = value(ImportField::Duration)
+        .map(|v| {
+            parse_import_duration(v, options.numbers_in_hours).ok_or_else(|| format!("Can't read duration \"{}\"", v))
+        })
+        .transpose()?;
+
+    let too_long = || format!("Session is longer than {} hours", MAX_SESSION_SECS / 3600);
+    let (end, secs) = match (start, end, secs) {
+        (Some(start), Some(end), _) if end < start => return Err("End is before start".to_string()),
+        (Some(start), Some(end), _) => (end, (end - start).num_seconds() as u64),
+        (_, _, Some(secs)) if secs > MAX_SESSION_SECS => return Err(too_long()),
+        (None, Some(end), Some(secs)) => (end, secs),
+        (Some(start), None, Some(secs)) => {
+            let end = start.checked_add_signed(Duration::seconds(secs as i64)).ok_or("End is out of range")?;
+            (end, secs)
+        }
+        _ => return Err("Needs two of start time, end time and duration".to_string()),
+    };
+    if secs > MAX_SESSION_SECS {
+        return Err(too_long());
+    }
+
+    let note = value(ImportField::Note).unwrap_or_default().to_string();
+    let progress = value(ImportField::Progress).unwrap_or_default().to_string();
+    Ok((game_name, end, secs, note, progress))
+}
+
+/// Whether two sessions of the same game are the same one, allowing for rounding by other trackers.
+fn same_session(a_end: NaiveDateTime, a_secs: u64, b_end: NaiveDateTime, b_secs: u64) -> bool {
+    (a_end - b_end).num_seconds().abs() <= DUPLICATE_TOLERANCE_SECS
+        && (a_secs as i64 - b_secs as i64).abs() <= DUPLICATE_TOLERANCE_SECS
+}
+
+/// Works out what importing `rows` would do, without writing anything.
+pub fn plan_import(rows: &[Vec<String>], options: &ImportOptions, games: &[Game], sessions: &[Session]) -> ImportPlan {
+    let mut plan = ImportPlan::default();
+    let skip = usize::from(options.has_header);
+
+    for (i, fields) in rows.iter().enumerate().skip(skip) {
+        let row = i + 1;
+        let (game_name, end, secs, note, progress) = match read_row(fields, options) {
+            Ok(read) => read,
+            Err(err) => {
+                plan.errors.push((row, err));
+                continue;
+            }
+        };
+
+        let game_id = find_game_by_name(games, &game_name).map(|g| g.id);
+        let stored = game_id.is_some_and(|id| {
+            sessions
+                .iter()
+                .filter(|s| s.game_id == id)
+                .any(|s| s.end_time().is_some_and(|e| same_session(e, s.duration_secs(), end, secs)))
+        });
+        let repeated = plan.sessions.iter().any(|p| {
+            p.game_name.trim().eq_ignore_ascii_case(game_name.trim()) && same_session(p.end, p.secs, end, secs)
+        });
+
+        if game_id.is_none() && !plan.new_games.iter().any(|n| n.trim().eq_ignore_ascii_case(game_name.trim())) {
+            plan.new_games.push(game_name.clone());
+        }
+        plan.sessions.push(PlannedSession {
+            row,
+            game_name,
+            game_id,
+            end,
+            secs,
+            note,
+            progress,
+            duplicate: stored || repeated,
+        });
+    }
+
+    plan
+}
+
+/// Numbers of a finished import.
+pub struct ImportSummary {
+    pub sessions: usize,
+    pub games: usize,
+}
+
+/// Writes the planned sessions, creating missing games, and updates the totals of every touched game.
+/// Games and sessions are saved together, so a failed import leaves both files as they were.
+pub fn apply_import(plan: &ImportPlan) -> Result<ImportSummary, String> {
+    let mut games = load_games();
+    let mut sessions = load_all_sessions();
+    let now = Local::now().format(DATE_FORMAT).to_string();
+    let mut created = 0;
+    let mut touched = HashSet::new();
+
+    for planned in plan.importable() {
+        let game_id = match find_game_by_name(&games, &planned.game_name) {
+            Some(game) => game.id,
+            None => {
+                let id = GameId::random();
+                let sort_index = games.iter().map(|g| g.sort_index + 1).max().unwrap_or(0);
+                games.push(Game {
+                    id,
+                    name: planned.game_name.trim().to_string(),
+                    path: String::new(),
+                    hours: format_duration(0),
+                    category_id: None,
+                    sort_index,
+                    added: now.clone(),
+                    last_played: String::new(),
+                    tags: Vec::new(),
+                    status: PlayStatus::Planned,
+                    status_history: Vec::new(),
+                    launch: LaunchSettings::default(),
+                    notes: String::new(),
+                    cover: String::new(),
+                });
+                created += 1;
+                id
+            }
+        };
+
+        sessions.push(Session {
+            id: SessionId::random(),
+            game_id,
+            date: format_date_time(planned.end),
+            duration: format_duration(planned.secs),
+            note: planned.note.clone(),
+            progress: planned.progress.clone(),
+        });
+        touched.insert(game_id);
+    }
+
+    for game in games.iter_mut().filter(|g| touched.contains(&g.id)) {
+        let own = sessions.iter().filter(|s| s.game_id == game.id);
+        game.hours = format_duration(own.clone().map(|s| s.duration_secs()).sum());
+        game.last_played = own.filter_map(|s| s.end_time()).max().map(format_date_time).unwrap_or_default();
+    }
+
+    save_games_and_sessions(&games, &sessions)?;
+    Ok(ImportSummary { sessions: plan.importable().count(), games: created })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|f| f.to_string()).collect()
+    }
+
+    fn options(mapping: Vec<ImportField>) -> ImportOptions {
+        ImportOptions { mapping, ..ImportOptions::default() }
+    }
+
+    #[test]
+    fn durations_in_every_notation() {
+        assert_eq!(parse_import_duration("1:30:15", true), Some(5415));
+        assert_eq!(parse_import_duration("2:05", true), Some(7500));
+        assert_eq!(parse_import_duration("1h 30m 0s", true), Some(5400));
+        assert_eq!(parse_import_duration("1,5", true), Some(5400));
+        assert_eq!(parse_import_duration("90", false), Some(90));
+    }
+
+    #[test]
+    fn negative_and_overflowing_durations_are_refused() {
+        assert_eq!(parse_import_duration("-1", true), None);
+        assert_eq!(parse_import_duration("-1:00", true), None);
+        assert_eq!(parse_import_duration("1:-5", true), None);
+        assert_eq!(parse_import_duration("18446744073709551615:00:00", true), None);
+        assert_eq!(parse_import_duration("1e300", true), None);
+        assert_eq!(parse_import_duration("inf", true), None);
+    }
+
+    #[test]
+    fn huge_durations_are_row_errors() {
+        let start_and_duration = options(vec![ImportField::Game, ImportField::Start, ImportField::Duration]);
+        for duration in ["5124095576030431:00:00", "99999999999999999h", "1e15", "73"] {
+            let fields = row(&["Saya", "2025-03-10 10:00:00", duration]);
+            assert!(read_row(&fields, &start_and_duration).is_err(), "{} should be refused", duration);
+        }
+
+        let start_and_end = options(vec![ImportField::Game, ImportField::Start, ImportField::End]);
+        let fields = row(&["Saya", "2020-01-01 10:00:00", "2025-01-01 10:00:00"]);
+        assert!(read_row(&fields, &start_and_end).is_err());
+    }
+
+    #[test]
+    fn end_follows_from_start_and_duration() {
+        let options = options(vec![ImportField::Game, ImportField::Start, ImportField::Duration]);
+        let (game, end, secs, _, _) = read_row(&row(&["Saya", "2025-03-10 23:30:00", "1"]), &options).unwrap();
+
+        assert_eq!(game, "Saya");
+        assert_eq!(format_date_time(end), "2025-03-11 00:30:00");
+        assert_eq!(secs, 3600);
+    }
+}
@@ -0,0 +1,43 @@
+use chrono::NaiveDateTime;
+
+/// Format of the session `date` field.
+pub const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Parses a duration string of the form `"Xh Ym Zs"` into total seconds.
+///
+/// Returns `0` for invalid or missing parts.
+pub fn parse_duration(s: &str) -> u64 {
+    let mut h: u64 = 0;
+    let mut m: u64 = 0;
+    let mut sec = 0;
+
+    for part in s.split_whitespace() {
+        if let Some(val) = part.strip_suffix('h') {
+            h = val.parse().unwrap_or(0);
+        } else if let Some(val) = part.strip_suffix('m') {
+            m = val.parse().unwrap_or(0);
+        } else if let Some(val) = part.strip_suffix('s') {
+            sec = val.parse().unwrap_or(0);
+        }
+    }
+
+    h.saturating_mul(3600).saturating_add(m.saturating_mul(60)).saturating_add(sec)
+}
+
+/// Formats a duration in seconds into a human-readable string `"Xh Ym Zs"`.
+pub fn format_duration(seconds: u64) -> String {
+    let h = seconds / 3600;
+    let m = (seconds % 3600) / 60;
+    let s = seconds % 60;
+    format!("{}h {}m {}s", h, m, s)
+}
+
+/// Parses a date-time string in [`DATE_FORMAT`].
+pub fn parse_date_time(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s.trim(), DATE_FORMAT).ok()
+}
+
+/// Formats a date-time into [`DATE_FORMAT`].
+pub fn format_date_time(dt: NaiveDateTime) -> String {
+    dt.format(DATE_FORMAT).to_string()
+}
@@ -1,5 +1,15 @@
-use crate::core::json_storage::{Game, delete_sessions_for_game, load_games, save_games};
+use crate::core::covers::delete_cover_file;
+use crate::core::duration::DATE_FORMAT;
+use crate::core::goals::{delete_goals_for_game, reassign_goals};
+use crate::core::json_storage::{
+    CategoryId, Game, GameId, LaunchSettings, PlayStatus, Session, StatusChange, delete_sessions_for_game,
+    load_all_sessions, load_games, load_goals, record_deleted_game, save_games, save_games_and_sessions,
+};
+use crate::core::library_merge::merged_history;
+use crate::core::reminders::{delete_limits_for_game, reassign_limits};
+use crate::core::session_crud::set_game_totals;
 use crate::ui::app::TimeTrackerApp;
+use chrono::Local;
 
 /// Add new game
 pub fn add_new_game(app: &mut TimeTrackerApp, file_path: String) {
@@ -17,8 +27,23 @@ pub fn add_new_game(app: &mut TimeTrackerApp, file_path: String) {
     }
 
     // Create new game object
-    let new_id = games.iter().map(|g| g.id).max().unwrap_or(0) + 1;
-    let new_game = Game { id: new_id, name: game_name, path: file_path, hours: "0h 0m 0s".to_string() };
+    let sort_index = games.iter().map(|g| g.sort_index + 1).max().unwrap_or(0);
+    let new_game = Game {
+        id: GameId::random(),
+        name: game_name,
+        path: file_path,
+        hours: "0h 0m 0s".to_string(),
+        category_id: None,
+        sort_index,
+        added: Local::now().format(DATE_FORMAT).to_string(),
+        last_played: String::new(),
+        tags: Vec::new(),
+        status: PlayStatus::Planned,
+        status_history: Vec::new(),
+        launch: LaunchSettings::default(),
+        notes: String::new(),
+        cover: String::new(),
+    };
 
     // Save
     games.push(new_game);
@@ -31,19 +56,27 @@ pub fn add_new_game(app: &mut TimeTrackerApp, file_path: String) {
 }
 
 /// Removes a game and its associated sessions
-pub fn delete_game_and_sessions(game_id: u32, app: &mut TimeTrackerApp) {
+pub fn delete_game_and_sessions(game_id: GameId, app: &mut TimeTrackerApp) {
     let mut games = load_games();
+    if let Some(game) = games.iter().find(|g| g.id == game_id) {
+        delete_cover_file(&game.cover);
+    }
     games.retain(|g| g.id != game_id);
     save_games(&games);
+    record_deleted_game(game_id);
 
     let _ = delete_sessions_for_game(game_id);
+    delete_goals_for_game(game_id);
+    delete_limits_for_game(game_id);
 
     app.state.show_confirm_delete_window = None;
     app.state.games = games;
+    app.state.goals = load_goals();
+    app.goal_header = None;
 }
 
 /// Rename game
-pub fn rename_game(game_id: u32, new_name: String) {
+pub fn rename_game(game_id: GameId, new_name: String) {
     let mut games = load_games();
 
     if let Some(game) = games.iter_mut().find(|g| g.id == game_id) {
@@ -52,3 +85,262 @@ pub fn rename_game(game_id: u32, new_name: String) {
 
     save_games(&games);
 }
+
+/// Updates the editable fields shown in the game details window.
+pub fn update_game_details(game_id: GameId, name: String, path: String, launch: LaunchSettings, notes: String) {
+    let mut games = load_games();
+
+    if let Some(game) = games.iter_mut().find(|g| g.id == game_id) {
+        game.name = name;
+        game.path = path;
+        game.launch = launch;
+        game.notes = notes;
+    }
+
+    save_games(&games);
+}
+
+/// Changes the play status of a game and records when it happened.
+/// Returns the recorded change, or `None` if the status stayed the same.
+pub fn set_game_status(game_id: GameId, status: PlayStatus) -> Option<StatusChange> {
+    let mut games = load_games();
+
+    let game = games.iter_mut().find(|g| g.id == game_id).filter(|g| g.status != status)?;
+    let change = StatusChange { status, date: Local::now().format(DATE_FORMAT).to_string() };
+    game.status = status;
+    game.status_history.push(change.clone());
+    save_games(&games);
+
+    Some(change)
+}
+
+/// Merges a duplicate library entry into another one.
+///
+/// All sessions of `donor_id` are moved to `target_id`, the target gets the chosen
+/// name and path, the donor's tags and status changes with the status of the latest one,
+/// its total time is recomputed, and the donor entry is deleted.
+pub fn merge_games(
+    donor_id: GameId,
+    target_id: GameId,
+    name: String,
+    path: String,
+    app: &mut TimeTrackerApp,
+) -> Result<(), String> {
+    if donor_id == target_id {
+        return Err("Choose two different games to merge".to_string());
+    }
+
+    let mut games = load_games();
+    let mut sessions = load_all_sessions();
+    let unused_cover = merge_into(&mut games, &mut sessions, donor_id, target_id, name, path)?;
+    // Sessions move together with the games, so a failed save leaves both games as they were
+    save_games_and_sessions(&games, &sessions)?;
+
+    delete_cover_file(&unused_cover);
+    record_deleted_game(donor_id);
+    reassign_goals(donor_id, target_id);
+    reassign_limits(donor_id, target_id);
+
+    // Windows pointing at the donor would show nothing now
+    if app.state.show_sessions_window == Some(donor_id) {
+        app.state.show_sessions_window = Some(target_id);
+    }
+    if app.state.editing_name == Some(donor_id) {
+        app.state.editing_name = None;
+    }
+    app.state.merge_games_draft = None;
+    app.state.games = load_games();
+    app.state.goals = load_goals();
+    app.goal_header = None;
+    Ok(())
+}
+
+/// Does the work of [`merge_games`] on loaded games and sessions.
+/// Returns the donor's cover if the target keeps its own, so the caller can delete it once saved.
+fn merge_into(
+    games: &mut Vec<Game>,
+    sessions: &mut [Session],
+    donor_id: GameId,
+    target_id: GameId,
+    name: String,
+    path: String,
+) -> Result<String, String> {
+    let donor = games.iter().find(|g| g.id == donor_id).cloned();
+    let Some(donor) = donor.filter(|_| games.iter().any(|g| g.id == target_id)) else {
+        return Err("Game not found".to_string());
+    };
+
+    for session in sessions.iter_mut().filter(|s| s.game_id == donor_id) {
+        session.game_id = target_id;
+    }
+
+    let mut unused_cover = String::new();
+    if let Some(target) = games.iter_mut().find(|g| g.id == target_id) {
+        target.name = name;
+        target.path = path;
+        for tag in donor.tags {
+            if !target.tags.contains(&tag) {
+                target.tags.push(tag);
+            }
+        }
+        target.status_history = merged_history(&target.status_history, &donor.status_history);
+        if let Some(latest) = target.status_history.last() {
+            target.status = latest.status;
+        }
+        // Keep the donor's cover only if the target has none
+        if target.cover.is_empty() {
+            target.cover = donor.cover;
+        } else {
+            unused_cover = donor.cover;
+        }
+        set_game_totals(target, sessions);
+    }
+    games.retain(|g| g.id != donor_id);
+    Ok(unused_cover)
+}
+
+/// Rewrites `sort_index` of games to match their order in the list and saves them.
+fn save_games_order(games: &mut [Game]) {
+    for (index, game) in games.iter_mut().enumerate() {
+        game.sort_index = index as u32;
+    }
+    save_games(games);
+}
+
+/// Moves a game in the list and into the category of its new place.
+///
+/// The game is put right before `before_id`, or after the last game of `category_id`
+/// when `before_id` is `None`.
+pub fn move_game(game_id: GameId, before_id: Option<GameId>, category_id: Option<CategoryId>) {
+    let mut games = load_games();
+    let Some(position) = games.iter().position(|g| g.id == game_id) else {
+        return;
+    };
+
+    let mut game = games.remove(position);
+    game.category_id = category_id;
+
+    let insert_at = match before_id {
+        Some(before_id) => games.iter().position(|g| g.id == before_id).unwrap_or(games.len()),
+        None => games.iter().rposition(|g| g.category_id == category_id).map_or(games.len(), |i| i + 1),
+    };
+    games.insert(insert_at, game);
+
+    save_games_order(&mut games);
+}
+
+/// Swaps a game with its previous (`up`) or next neighbour in the same category.
+///
+/// Only games in `visible_ids` count as neighbours, so games hidden by the filters are stepped over.
+pub fn move_game_step(game_id: GameId, up: bool, visible_ids: &[GameId]) {
+    let mut games = load_games();
+    let Some(position) = games.iter().position(|g| g.id == game_id) else {
+        return;
+    };
+
+    let category_id = games[position].category_id;
+    let is_neighbour = |g: &Game| g.category_id == category_id && visible_ids.contains(&g.id);
+    let neighbour = if up {
+        games[..position].iter().rposition(is_neighbour)
+    } else {
+        games[position + 1..].iter().position(is_neighbour).map(|i| position + 1 + i)
+    };
+
+    if let Some(neighbour) = neighbour {
+        games.swap(position, neighbour);
+        save_games_order(&mut games);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::json_storage::SessionId;
+
+    fn game(name: &str, tags: &[&str], history: &[(PlayStatus, &str)], cover: &str) -> Game {
+        Game {
+            id: GameId::random(),
+            name: name.to_string(),
+            path: String::new(),
+            hours: "0h 0m 0s".to_string(),
+            category_id: None,
+            sort_index: 0,
+            added: String::new(),
+            last_played: String::new(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            status: history.last().map_or(PlayStatus::Planned, |(status, _)| *status),
+            status_history: history
+                .iter()
+                .map(|(status, date)| StatusChange { status: *status, date: date.to_string() })
+                .collect(),
+            launch: LaunchSettings::default(),
+            notes: String::new(),
+            cover: cover.to_string(),
+        }
+    }
+
+    fn session(game_id: GameId, date: &str, duration: &str) -> Session {
+        Session {
+            id: SessionId::random(),
+            game_id,
+            date: date.to_string(),
+            duration: duration.to_string(),
+            note: String::new(),
+            progress: String::new(),
+        }
+    }
+
+    #[test]
+    fn merging_moves_sessions_tags_and_history_to_the_target() {
+        let target =
+            game("Fate/stay night", &["type-moon"], &[(PlayStatus::Playing, "2025-01-05 20:00:00")], "covers/fsn.png");
+        let donor = game(
+            "Fate stay night [Realta Nua]",
+            &["type-moon", "nukige"],
+            &[(PlayStatus::Completed, "2025-02-01 23:00:00")],
+            "covers/realta.png",
+        );
+        let other = game("Tsukihime", &[], &[], "");
+        let mut games = vec![target.clone(), donor.clone(), other.clone()];
+        let mut sessions = vec![
+            session(target.id, "2025-01-05 22:00:00", "2h 0m 0s"),
+            session(donor.id, "2025-02-01 23:00:00", "1h 30m 0s"),
+            session(other.id, "2025-03-01 23:00:00", "5h 0m 0s"),
+        ];
+
+        let unused_cover = merge_into(
+            &mut games,
+            &mut sessions,
+            donor.id,
+            target.id,
+            "Fate/stay night".to_string(),
+            "/vn/fsn.exe".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(games.len(), 2);
+        let merged = games.iter().find(|g| g.id == target.id).unwrap();
+        assert_eq!(merged.path, "/vn/fsn.exe");
+        assert_eq!(merged.tags, ["type-moon", "nukige"]);
+        assert_eq!(merged.status_history.len(), 2);
+        assert!(merged.status == PlayStatus::Completed);
+        assert_eq!(merged.hours, "3h 30m 0s");
+        assert_eq!(merged.last_played, "2025-02-01 23:00:00");
+        assert_eq!(merged.cover, "covers/fsn.png");
+        assert_eq!(unused_cover, "covers/realta.png");
+        assert!(sessions[..2].iter().all(|s| s.game_id == target.id));
+        assert!(sessions[2].game_id == other.id);
+    }
+
+    #[test]
+    fn merging_with_a_missing_game_changes_nothing() {
+        let target = game("Ever17", &[], &[], "");
+        let mut games = vec![target.clone()];
+        let mut sessions = vec![session(target.id, "2025-01-05 22:00:00", "2h 0m 0s")];
+
+        assert!(
+            merge_into(&mut games, &mut sessions, GameId::random(), target.id, String::new(), String::new()).is_err()
+        );
+        assert_eq!(games[0].name, "Ever17");
+    }
+}
@@ -0,0 +1,195 @@
+use crate::core::duration::parse_duration;
+use crate::core::json_storage::{Game, PlayStatus};
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::path::Path;
+
+/// Column the games table can be sorted by.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Name,
+    TotalTime,
+    LastPlayed,
+    DateAdded,
+}
+
+impl SortColumn {
+    pub const ALL: [SortColumn; 4] =
+        [SortColumn::Name, SortColumn::TotalTime, SortColumn::LastPlayed, SortColumn::DateAdded];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortColumn::Name => "Name",
+            SortColumn::TotalTime => "Total time",
+            SortColumn::LastPlayed => "Last played",
+            SortColumn::DateAdded => "Date added",
+        }
+    }
+
+    /// Compares two games by this column, ascending.
+    pub fn compare(&self, a: &Game, b: &Game) -> Ordering {
+        match self {
+            SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortColumn::TotalTime => parse_duration(&a.hours).cmp(&parse_duration(&b.hours)),
+            // Dates are stored as "%Y-%m-%d %H:%M:%S", so string order is chronological
+            SortColumn::LastPlayed => a.last_played.cmp(&b.last_played),
+            SortColumn::DateAdded => a.added.cmp(&b.added),
+        }
+    }
+}
+
+/// Scores how well `query` fuzzy-matches `text` (case-insensitive).
+///
+/// Every query character must appear in `text` in the same order. Consecutive
+/// matches and matches at word starts score higher. Returns `None` if there is no match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = pos + text[pos..].iter().position(|c| *c == q)?;
+
+        score += 1;
+        if previous_match.is_some_and(|p| p + 1 == found) {
+            score += 3;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 2;
+        }
+
+        previous_match = Some(found);
+        pos = found + 1;
+    }
+
+    Some(score)
+}
+
+/// Scores how well a game matches the search query by name or executable file name,
+/// or `None` if neither matches.
+///
+/// Only the file name of the path is searched; the folders would match almost any query.
+pub fn game_score(game: &Game, query: &str) -> Option<i32> {
+    let file_name = Path::new(&game.path).file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    fuzzy_score(query, &game.name).max(fuzzy_score(query, file_name))
+}
+
+/// Checks whether a game matches the search query by name or executable file name.
+pub fn game_matches(game: &Game, query: &str) -> bool {
+    query.trim().is_empty() || game_score(game, query).is_some()
+}
+
+/// Checks whether a game has all (`match_all`) or any of the given tags.
+/// An empty tag list matches every game.
+pub fn tags_match(game: &Game, tags: &[String], match_all: bool) -> bool {
+    if tags.is_empty() {
+        return true;
+    }
+    if match_all { tags.iter().all(|t| game.tags.contains(t)) } else { tags.iter().any(|t| game.tags.contains(t)) }
+}
+
+/// What the games table is narrowed down to.
+pub struct GameFilter<'a> {
+    /// Fuzzy search on name and executable file name.
+    pub query: &'a str,
+    pub tags: &'a [String],
+    /// `true` to require all tags (AND), `false` for any of them (OR).
+    pub match_all_tags: bool,
+    pub status: Option<PlayStatus>,
+}
+
+impl GameFilter<'_> {
+    /// Whether the filter hides anything at all.
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty() || !self.tags.is_empty() || self.status.is_some()
+    }
+
+    pub fn matches(&self, game: &Game) -> bool {
+        game_matches(game, self.query)
+            && tags_match(game, self.tags, self.match_all_tags)
+            && self.status.is_none_or(|s| game.status == s)
+    }
+}
+
+/// Returns indices of games passing the filter, ordered by `sort`. Without a sort column
+/// the best search matches come first, ties and an empty search keep the list order.
+pub fn visible_game_indices(
+    games: &[Game],
+    filter: &GameFilter,
+    sort: Option<SortColumn>,
+    descending: bool,
+) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..games.len()).filter(|&i| filter.matches(&games[i])).collect();
+
+    if let Some(column) = sort {
+        indices.sort_by(|&a, &b| {
+            let ordering = column.compare(&games[a], &games[b]);
+            if descending { ordering.reverse() } else { ordering }
+        });
+    } else if !filter.query.trim().is_empty() {
+        indices.sort_by_cached_key(|&i| Reverse(game_score(&games[i], filter.query)));
+    }
+
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::json_storage::{GameId, LaunchSettings};
+
+    fn game(name: &str, path: &str) -> Game {
+        Game {
+            id: GameId::random(),
+            name: name.to_string(),
+            path: path.to_string(),
+            hours: "0h 0m 0s".to_string(),
+            category_id: None,
+            sort_index: 0,
+            added: String::new(),
+            last_played: String::new(),
+            tags: Vec::new(),
+            status: PlayStatus::Planned,
+            status_history: Vec::new(),
+            launch: LaunchSettings::default(),
+            notes: String::new(),
+            cover: String::new(),
+        }
+    }
+
+    fn filter(query: &str) -> GameFilter<'_> {
+        GameFilter { query, tags: &[], match_all_tags: false, status: None }
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_consecutive_and_word_start_matches() {
+        assert_eq!(fuzzy_score("sk", "Steins;Gate"), None);
+        assert!(fuzzy_score("sg", "Steins;Gate") > fuzzy_score("sg", "Sweet Pool Magnum"));
+        assert!(fuzzy_score("gate", "Steins;Gate") > fuzzy_score("gate", "Grisaia: Phantom Trigger"));
+    }
+
+    #[test]
+    fn path_matches_only_by_file_name() {
+        let saya = game("Saya no Uta", "/home/user/games/visual novels/saya/saya.exe");
+
+        assert!(game_matches(&saya, "saya.exe"));
+        assert!(!game_matches(&saya, "visual novels"));
+        assert!(!game_matches(&saya, "home"));
+    }
+
+    #[test]
+    fn search_results_are_ranked_by_score() {
+        let games = [
+            game("Muv-Luv Alternative", "/games/alternative.exe"),
+            game("Ever17", "/games/ever17.exe"),
+            game("Muv-Luv", "/games/muvluv.exe"),
+            game("Rewrite", "/games/rewrite.exe"),
+        ];
+
+        assert_eq!(visible_game_indices(&games, &filter("muv"), None, false), vec![0, 2]);
+        assert_eq!(visible_game_indices(&games, &filter("re"), None, false), vec![3, 1, 0]);
+        assert_eq!(visible_game_indices(&games, &filter("re"), Some(SortColumn::Name), false), vec![1, 0, 3]);
+        assert_eq!(visible_game_indices(&games, &filter(""), None, false), vec![0, 1, 2, 3]);
+    }
+}
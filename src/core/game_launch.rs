@@ -1,7 +1,16 @@
-use crate::core::json_storage::{Game, Session, load_games, save_games, save_session};
+use crate::core::duration::{DATE_FORMAT, format_duration, parse_duration};
+use crate::core::game_crud::set_game_status;
+use crate::core::json_storage::{
+    Game, PlayStatus, Session, SessionId, load_games, load_limits, save_games, save_session,
+};
+use crate::core::reminders::{NotificationBus, ReminderTracker, SessionBus};
 use crate::ui::app::GameUpdate;
 use chrono::Local;
-use std::{process::Command, sync::mpsc::Sender, thread, time::Instant};
+use eframe::egui::Context;
+use std::io;
+use std::process::{Child, Command, ExitStatus};
+use std::time::{Duration, Instant};
+use std::{sync::mpsc::Sender, thread};
 
 /// Launch a game process asynchronously, track its playtime, and persist the data.
 ///
@@ -12,24 +21,51 @@ use std::{process::Command, sync::mpsc::Sender, thread, time::Instant};
 ///
 /// # Arguments
 ///
-/// * `current_game` - Mutable reference to the game being launched. Only `hours` is updated after the game finishes.
-/// * `updates` - Channel sender to notify the UI of updated game hours.
-pub fn start_game(current_game: &mut Game, updates: Sender<GameUpdate>) {
+/// * `current_game` - The game being launched. A planned game is moved to "playing" once its process
+///   started.
+/// * `updates` - Channel sender to notify the UI of the status change, updated game hours and session reminders.
+/// * `ctx` - UI context, repainted so updates show up without user input.
+pub fn start_game(current_game: &Game, updates: Sender<GameUpdate>, ctx: Context) {
     let game_id = current_game.id;
+    let name = current_game.name.clone();
     let path = current_game.path.clone();
+    let arguments = split_arguments(&current_game.launch.arguments);
+    let working_dir = current_game.launch.working_dir.trim().to_string();
+    let planned = current_game.status == PlayStatus::Planned;
 
     thread::spawn(move || {
         let start = Instant::now();
+        let mut reminders = ReminderTracker::new(game_id, name, load_limits());
 
-        match Command::new(&path).spawn().and_then(|mut child| child.wait()) {
+        let mut command = Command::new(&path);
+        command.args(&arguments);
+        if !working_dir.is_empty() {
+            command.current_dir(&working_dir);
+        }
+
+        let waited = command.spawn().and_then(|mut child| {
+            // Only a game that really started counts as being played
+            if planned && let Some(change) = set_game_status(game_id, PlayStatus::Playing) {
+                let _ = updates.send(GameUpdate::Started { game_id, change });
+                ctx.request_repaint();
+            }
+            wait_with_reminders(&mut child, start, &mut reminders, &SessionBus, |message| {
+                let _ = updates.send(GameUpdate::Reminder { game_id, message });
+                ctx.request_repaint();
+            })
+        });
+
+        match waited {
             Ok(_) => {
                 let elapsed = start.elapsed().as_secs();
+                let ended = Local::now().format(DATE_FORMAT).to_string();
 
                 // Load games from JSON and update the played time
                 let mut stored_games = load_games();
                 if let Some(stored_game) = stored_games.iter_mut().find(|g| g.id == game_id) {
                     let total_secs = parse_duration(&stored_game.hours) + elapsed;
                     stored_game.hours = format_duration(total_secs);
+                    stored_game.last_played = ended.clone();
 
                     let updated_hours = stored_game.hours.clone();
 
@@ -37,14 +73,23 @@ pub fn start_game(current_game: &mut Game, updates: Sender<GameUpdate>) {
 
                     // Save session
                     let session = Session {
+                        id: SessionId::random(),
                         game_id,
-                        date: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                        date: ended,
                         duration: format_duration(elapsed),
+                        note: String::new(),
+                        progress: String::new(),
                     };
                     save_session(&session);
 
                     // Notify UI
-                    let _ = updates.send(GameUpdate { game_id, hours: updated_hours.clone() });
+                    let _ = updates.send(GameUpdate::Finished {
+                        game_id,
+                        hours: updated_hours.clone(),
+                        session_id: session.id,
+                        session_date: session.date.clone(),
+                    });
+                    ctx.request_repaint();
                 }
             }
             Err(err) => eprintln!("Failed to launch game {}: {:?}", path, err),
@@ -52,31 +97,45 @@ pub fn start_game(current_game: &mut Game, updates: Sender<GameUpdate>) {
     });
 }
 
-/// Parses a duration string of the form `"Xh Ym Zs"` into total seconds.
-///
-/// Returns `0` for invalid or missing parts.
-fn parse_duration(s: &str) -> u64 {
-    let mut h = 0;
-    let mut m = 0;
-    let mut sec = 0;
-
-    for part in s.split_whitespace() {
-        if let Some(val) = part.strip_suffix('h') {
-            h = val.parse().unwrap_or(0);
-        } else if let Some(val) = part.strip_suffix('m') {
-            m = val.parse().unwrap_or(0);
-        } else if let Some(val) = part.strip_suffix('s') {
-            sec = val.parse().unwrap_or(0);
+/// Waits for the game to exit, checking session reminders once a second and sending them to `bus`.
+fn wait_with_reminders(
+    child: &mut Child,
+    start: Instant,
+    reminders: &mut ReminderTracker,
+    bus: &dyn NotificationBus,
+    mut notify: impl FnMut(String),
+) -> io::Result<ExitStatus> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        for message in reminders.tick(start.elapsed().as_secs(), bus) {
+            notify(message);
         }
+        thread::sleep(Duration::from_secs(1));
     }
-
-    h * 3600 + m * 60 + sec
 }
 
-/// Formats a duration in seconds into a human-readable string `"Xh Ym Zs"`.
-fn format_duration(seconds: u64) -> String {
-    let h = seconds / 3600;
-    let m = (seconds % 3600) / 60;
-    let s = seconds % 60;
-    format!("{}h {}m {}s", h, m, s)
+/// Splits a command line into arguments on whitespace, keeping double-quoted parts together.
+fn split_arguments(s: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in s.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    arguments.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        arguments.push(current);
+    }
+
+    arguments
 }
@@ -0,0 +1,113 @@
+use crate::core::json_storage::{GameId, GoalId, GoalPeriod, PlayGoal, Session, load_goals, save_goals};
+use crate::core::statistics::{DateRange, Period, playtime_per_day, playtime_per_week};
+use chrono::{Days, NaiveDate};
+use std::collections::BTreeSet;
+
+/// Consecutive periods a goal was reached in.
+#[derive(Clone, Copy, Default)]
+pub struct Streak {
+    /// Run ending with the current period, or the previous one while the current isn't reached yet.
+    pub current: u32,
+    pub longest: u32,
+}
+
+/// Adds a new goal and returns its id.
+pub fn add_goal(game_id: Option<GameId>, period: GoalPeriod, minutes: u64) -> GoalId {
+    let mut goals = load_goals();
+
+    let new_id = GoalId::random();
+    goals.push(PlayGoal { id: new_id, game_id, period, minutes });
+    save_goals(&goals);
+
+    new_id
+}
+
+/// Saves changed game, period or target of an existing goal.
+pub fn update_goal(goal: &PlayGoal) {
+    let mut goals = load_goals();
+
+    if let Some(existing) = goals.iter_mut().find(|g| g.id == goal.id) {
+        *existing = goal.clone();
+    }
+
+    save_goals(&goals);
+}
+
+/// Removes a goal.
+pub fn delete_goal(goal_id: GoalId) {
+    let mut goals = load_goals();
+    goals.retain(|g| g.id != goal_id);
+    save_goals(&goals);
+}
+
+/// Removes the goals of a deleted game.
+pub fn delete_goals_for_game(game_id: GameId) {
+    let mut goals = load_goals();
+    goals.retain(|g| g.game_id != Some(game_id));
+    save_goals(&goals);
+}
+
+/// Points the goals of a merged game at the game it was merged into.
+pub fn reassign_goals(from_game_id: GameId, to_game_id: GameId) {
+    let mut goals = load_goals();
+
+    for goal in goals.iter_mut().filter(|g| g.game_id == Some(from_game_id)) {
+        goal.game_id = Some(to_game_id);
+    }
+
+    save_goals(&goals);
+}
+
+/// The day or week (starting on Monday) containing `day`.
+pub fn goal_period_range(period: GoalPeriod, day: NaiveDate) -> DateRange {
+    match period {
+        GoalPeriod::Daily => DateRange::days(day, day),
+        GoalPeriod::Weekly => Period::ThisWeek.range(day),
+    }
+}
+
+/// Sessions the goal counts: those of its game, or all of them for a library goal.
+fn counted_sessions(goal: &PlayGoal, sessions: &[Session]) -> Vec<Session> {
+    sessions.iter().filter(|s| goal.game_id.is_none_or(|id| s.game_id == id)).cloned().collect()
+}
+
+/// Seconds played towards the goal in the period containing `day`.
+pub fn goal_progress(goal: &PlayGoal, sessions: &[Session], day: NaiveDate) -> u64 {
+    let range = goal_period_range(goal.period, day);
+    playtime_per_day(&counted_sessions(goal, sessions), &range).values().sum()
+}
+
+/// First days of the periods (days, or Mondays for weekly goals) in which the goal was reached.
+pub fn periods_met(goal: &PlayGoal, sessions: &[Session]) -> BTreeSet<NaiveDate> {
+    let sessions = counted_sessions(goal, sessions);
+    let target = goal.minutes * 60;
+    let periods = match goal.period {
+        GoalPeriod::Daily => playtime_per_day(&sessions, &DateRange::all_time()),
+        GoalPeriod::Weekly => playtime_per_week(&sessions, &DateRange::all_time()),
+    };
+
+    periods.into_iter().filter(|(_, secs)| *secs >= target).map(|(start, _)| start).collect()
+}
+
+/// Computes the current and longest run of consecutive periods the goal was reached in.
+pub fn goal_streak(goal: &PlayGoal, sessions: &[Session], today: NaiveDate) -> Streak {
+    let step = Days::new(if goal.period == GoalPeriod::Weekly { 7 } else { 1 });
+    let met = periods_met(goal, sessions);
+
+    let mut streak = Streak::default();
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for start in &met {
+        run = if previous.is_some_and(|p| p + step == *start) { run + 1 } else { 1 };
+        streak.longest = streak.longest.max(run);
+        previous = Some(*start);
+    }
+
+    // The last run is still going if it reaches this period or the one before
+    let current_start = goal_period_range(goal.period, today).start.date();
+    if previous.is_some_and(|last| last == current_start || last + step == current_start) {
+        streak.current = run;
+    }
+
+    streak
+}
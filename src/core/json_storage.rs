@@ -1,26 +1,307 @@
-use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use crate::core::duration::{parse_date_time, parse_duration};
+use crate::core::sync_storage;
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Deserializer, Serialize, de::DeserializeOwned};
 use std::{
-    fs::{File, OpenOptions},
-    io::{BufReader, BufWriter},
+    collections::HashMap,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+    str::FromStr,
 };
+use uuid::Uuid;
+
+/// Defines a random identifier that also reads the numbers older versions stored.
+macro_rules! random_id {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        #[serde(transparent)]
+        pub struct $name(Uuid);
+
+        impl $name {
+            pub fn random() -> Self {
+                $name(Uuid::new_v4())
+            }
+
+            /// Stand-in for a numeric id written before ids were random.
+            pub fn legacy(id: u64) -> Self {
+                $name(Uuid::from_u128(id as u128))
+            }
+
+            /// Whether this is a numeric id `migrate_legacy_ids` hasn't replaced yet.
+            pub fn is_legacy(&self) -> bool {
+                self.0.as_u128() <= u32::MAX as u128
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = uuid::Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Uuid::parse_str(s).map($name)
+            }
+        }
+
+        /// Reads both random ids and the numbers older versions stored.
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                #[derive(Deserialize)]
+                #[serde(untagged)]
+                enum Stored {
+                    Random(Uuid),
+                    Legacy(u64),
+                }
+
+                Ok(match Stored::deserialize(deserializer)? {
+                    Stored::Random(id) => $name(id),
+                    Stored::Legacy(id) => $name::legacy(id),
+                })
+            }
+        }
+    };
+}
+
+random_id!(
+    /// Identifier of a game. Random, so games added on different machines never share one.
+    GameId
+);
+random_id!(
+    /// Identifier of a category, random like [`GameId`].
+    CategoryId
+);
+random_id!(
+    /// Identifier of a playtime goal, random like [`GameId`].
+    GoalId
+);
+random_id!(
+    /// Identifier of a session reminder, random like [`GameId`].
+    LimitId
+);
+
+/// Identifier of a session. Nil for sessions stored before sessions had ids.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+#[serde(transparent)]
+pub struct SessionId(Uuid);
+
+impl SessionId {
+    pub fn random() -> Self {
+        SessionId(Uuid::new_v4())
+    }
+
+    pub fn is_nil(&self) -> bool {
+        self.0.is_nil()
+    }
+}
+
+impl fmt::Display for SessionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Game {
-    pub id: u32,
+    pub id: GameId,
     pub name: String,
     pub path: String,
     pub hours: String,
+    /// Category the game is grouped under, `None` for uncategorized.
+    #[serde(default)]
+    pub category_id: Option<CategoryId>,
+    /// Position in the games list, lower comes first.
+    #[serde(default)]
+    pub sort_index: u32,
+    /// When the game was added to the library, empty for games added before this was tracked.
+    #[serde(default)]
+    pub added: String,
+    /// End of the latest session, empty if never played.
+    #[serde(default)]
+    pub last_played: String,
+    /// Free-form labels, e.g. "otome" or "JP-only".
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub status: PlayStatus,
+    /// Every status change, oldest first.
+    #[serde(default)]
+    pub status_history: Vec<StatusChange>,
+    #[serde(default)]
+    pub launch: LaunchSettings,
+    /// Free-form notes about the game.
+    #[serde(default)]
+    pub notes: String,
+    /// Cached cover thumbnail, relative to the data directory. Empty if not set.
+    #[serde(default)]
+    pub cover: String,
+}
+
+/// Extra options used when starting a game.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct LaunchSettings {
+    /// Command line arguments, double quotes group words.
+    pub arguments: String,
+    /// Directory to start the game in, empty to inherit the tracker's one.
+    pub working_dir: String,
+}
+
+/// Where the player is with a game.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlayStatus {
+    #[default]
+    Planned,
+    Playing,
+    OnHold,
+    Completed,
+    Dropped,
 }
 
+impl PlayStatus {
+    pub const ALL: [PlayStatus; 5] =
+        [PlayStatus::Planned, PlayStatus::Playing, PlayStatus::OnHold, PlayStatus::Completed, PlayStatus::Dropped];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlayStatus::Planned => "Planned",
+            PlayStatus::Playing => "Playing",
+            PlayStatus::OnHold => "On hold",
+            PlayStatus::Completed => "Completed",
+            PlayStatus::Dropped => "Dropped",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            PlayStatus::Planned => "🕓",
+            PlayStatus::Playing => "▶",
+            PlayStatus::OnHold => "⏸",
+            PlayStatus::Completed => "✔",
+            PlayStatus::Dropped => "✖",
+        }
+    }
+}
+
+/// A status a game was moved to, and when.
 #[derive(Serialize, Deserialize, Clone)]
+pub struct StatusChange {
+    pub status: PlayStatus,
+    pub date: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Category {
+    pub id: CategoryId,
+    pub name: String,
+}
+
+/// How often a playtime goal has to be reached.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GoalPeriod {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl GoalPeriod {
+    pub const ALL: [GoalPeriod; 2] = [GoalPeriod::Daily, GoalPeriod::Weekly];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GoalPeriod::Daily => "a day",
+            GoalPeriod::Weekly => "a week",
+        }
+    }
+}
+
+/// Playtime target, e.g. 30 minutes a day.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PlayGoal {
+    pub id: GoalId,
+    /// Game the goal counts, `None` for the whole library.
+    pub game_id: Option<GameId>,
+    pub period: GoalPeriod,
+    pub minutes: u64,
+}
+
+/// Whether a reminder fires once or keeps repeating.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LimitKind {
+    /// Once the session reaches the limit, e.g. "remind me after 2 hours".
+    #[default]
+    After,
+    /// Every time the interval passes, e.g. "take a break every 45 minutes".
+    Every,
+}
+
+impl LimitKind {
+    pub const ALL: [LimitKind; 2] = [LimitKind::After, LimitKind::Every];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LimitKind::After => "once after",
+            LimitKind::Every => "every",
+        }
+    }
+}
+
+/// Session length reminder.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PlayLimit {
+    pub id: LimitId,
+    /// Game the limit applies to, `None` for every game.
+    pub game_id: Option<GameId>,
+    pub kind: LimitKind,
+    pub minutes: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct Session {
-    pub game_id: u32,
+    #[serde(default)]
+    pub id: SessionId,
+    pub game_id: GameId,
     pub date: String,
     pub duration: String,
+    /// Free-form note about the session.
+    #[serde(default)]
+    pub note: String,
+    /// Where the player stopped (route, chapter, save slot).
+    #[serde(default)]
+    pub progress: String,
+}
+
+impl Session {
+    /// Session length in seconds.
+    pub fn duration_secs(&self) -> u64 {
+        parse_duration(&self.duration)
+    }
+
+    /// Moment the session ended (the stored `date`).
+    pub fn end_time(&self) -> Option<NaiveDateTime> {
+        parse_date_time(&self.date)
+    }
+
+    /// Moment the session started, derived from the end time and duration.
+    /// `None` if the date can't be parsed or the duration reaches out of the calendar.
+    pub fn start_time(&self) -> Option<NaiveDateTime> {
+        let duration = Duration::try_seconds(i64::try_from(self.duration_secs()).ok()?)?;
+        self.end_time()?.checked_sub_signed(duration)
+    }
 }
 
 const GAMES_FILE: &str = "games.json";
-const SESSIONS_FILE: &str = "sessions.json";
+pub const SESSIONS_FILE: &str = "sessions.json";
+const CATEGORIES_FILE: &str = "categories.json";
+const GOALS_FILE: &str = "goals.json";
+const LIMITS_FILE: &str = "limits.json";
+/// Ids of deleted games, so a sync tool's copy of `games.json` doesn't bring them back.
+pub const DELETED_GAMES_FILE: &str = "deleted-games.json";
 
 /// Generic helper to load a JSON file into a vector of T.
 /// Returns an empty Vec if the file does not exist or parsing fails.
@@ -41,9 +322,26 @@ fn save_to_file<T: Serialize>(path: &str, data: &[T]) {
     }
 }
 
-/// Loads all games from disk.
+/// Writes a slice as pretty JSON next to `path` and returns the temporary file's name.
+fn write_temp_file<T: Serialize>(path: &str, data: &[T]) -> Result<String, String> {
+    let temp = format!("{}.tmp", path);
+    let file = File::create(&temp).map_err(|e| format!("Can't write {}: {}", temp, e))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, data)
+        .map_err(|e| e.to_string())
+        .and_then(|_| writer.flush().map_err(|e| e.to_string()))
+        .map_err(|e| {
+            let _ = fs::remove_file(&temp);
+            format!("Can't write {}: {}", temp, e)
+        })?;
+    Ok(temp)
+}
+
+/// Loads all games from disk, ordered by `sort_index`.
 pub fn load_games() -> Vec<Game> {
-    load_from_file(GAMES_FILE)
+    let mut games: Vec<Game> = load_from_file(GAMES_FILE);
+    games.sort_by_key(|g| g.sort_index);
+    games
 }
 
 /// Saves all games to disk.
@@ -51,29 +349,263 @@ pub fn save_games(games: &[Game]) {
     save_to_file(GAMES_FILE, games);
 }
 
+/// Loads all categories from disk.
+pub fn load_categories() -> Vec<Category> {
+    load_from_file(CATEGORIES_FILE)
+}
+
+/// Saves all categories to disk.
+pub fn save_categories(categories: &[Category]) {
+    save_to_file(CATEGORIES_FILE, categories);
+}
+
+/// Loads all playtime goals from disk.
+pub fn load_goals() -> Vec<PlayGoal> {
+    load_from_file(GOALS_FILE)
+}
+
+/// Saves all playtime goals to disk.
+pub fn save_goals(goals: &[PlayGoal]) {
+    save_to_file(GOALS_FILE, goals);
+}
+
+/// Loads all session reminders from disk.
+pub fn load_limits() -> Vec<PlayLimit> {
+    load_from_file(LIMITS_FILE)
+}
+
+/// Saves all session reminders to disk.
+pub fn save_limits(limits: &[PlayLimit]) {
+    save_to_file(LIMITS_FILE, limits);
+}
+
+/// Loads the ids of deleted games.
+pub fn load_deleted_games() -> Vec<GameId> {
+    load_from_file(DELETED_GAMES_FILE)
+}
+
+/// Saves the ids of deleted games.
+pub fn save_deleted_games(ids: &[GameId]) {
+    save_to_file(DELETED_GAMES_FILE, ids);
+}
+
+/// Remembers that a game was deleted.
+pub fn record_deleted_game(id: GameId) {
+    let mut ids = load_deleted_games();
+    if !ids.contains(&id) {
+        ids.push(id);
+        save_deleted_games(&ids);
+    }
+}
+
 /// Loads all sessions for a specific game.
-pub fn load_sessions(game_id: u32) -> Vec<Session> {
-    load_from_file::<Session>(SESSIONS_FILE).into_iter().filter(|s| s.game_id == game_id).collect()
+pub fn load_sessions(game_id: GameId) -> Vec<Session> {
+    load_all_sessions().into_iter().filter(|s| s.game_id == game_id).collect()
+}
+
+/// Loads sessions of all games, merged from the device logs if the data folder uses them.
+pub fn load_all_sessions() -> Vec<Session> {
+    if sync_storage::is_enabled() {
+        sync_storage::merged_sessions(Path::new(sync_storage::LOGS_DIR))
+    } else {
+        load_from_file(SESSIONS_FILE)
+    }
 }
 
-/// Appends a new session to the session file.
+/// Overwrites the stored sessions with the given ones. With device logs only the differences are appended.
+pub fn save_all_sessions(sessions: &[Session]) {
+    if !sync_storage::is_enabled() {
+        save_to_file(SESSIONS_FILE, sessions);
+    } else if let Err(err) = sync_storage::record_sessions(sessions) {
+        eprintln!("Failed to save sessions: {}", err);
+    }
+}
+
+/// Saves games and sessions as one change: both are written to temporary files first,
+/// and the games file is put back if the sessions file can't be replaced.
+/// With device logs the games file is put back if the sessions can't be appended.
+pub fn save_games_and_sessions(games: &[Game], sessions: &[Session]) -> Result<(), String> {
+    if sync_storage::is_enabled() {
+        let games_temp = write_temp_file(GAMES_FILE, games)?;
+        let previous_games = fs::read(GAMES_FILE).ok();
+        if let Err(err) = fs::rename(&games_temp, GAMES_FILE) {
+            let _ = fs::remove_file(&games_temp);
+            return Err(format!("Can't replace {}: {}", GAMES_FILE, err));
+        }
+        return sync_storage::record_sessions(sessions).inspect_err(|_| match &previous_games {
+            Some(previous) => {
+                let _ = fs::write(GAMES_FILE, previous);
+            }
+            None => {
+                let _ = fs::remove_file(GAMES_FILE);
+            }
+        });
+    }
+
+    let games_temp = write_temp_file(GAMES_FILE, games)?;
+    let sessions_temp = write_temp_file(SESSIONS_FILE, sessions).inspect_err(|_| {
+        let _ = fs::remove_file(&games_temp);
+    })?;
+    let previous_games = fs::read(GAMES_FILE).ok();
+
+    if let Err(err) = fs::rename(&games_temp, GAMES_FILE) {
+        let _ = fs::remove_file(&games_temp);
+        let _ = fs::remove_file(&sessions_temp);
+        return Err(format!("Can't replace {}: {}", GAMES_FILE, err));
+    }
+    if let Err(err) = fs::rename(&sessions_temp, SESSIONS_FILE) {
+        match previous_games {
+            Some(previous) => {
+                let _ = fs::write(GAMES_FILE, previous);
+            }
+            None => {
+                let _ = fs::remove_file(GAMES_FILE);
+            }
+        }
+        let _ = fs::remove_file(&sessions_temp);
+        return Err(format!("Can't replace {}: {}", SESSIONS_FILE, err));
+    }
+
+    Ok(())
+}
+
+/// Appends a new session to the session file, or to this device's log.
 pub fn save_session(session: &Session) {
-    let mut sessions = load_from_file::<Session>(SESSIONS_FILE);
-    sessions.push(session.clone());
-    save_to_file(SESSIONS_FILE, &sessions);
+    if !sync_storage::is_enabled() {
+        let mut sessions = load_from_file::<Session>(SESSIONS_FILE);
+        sessions.push(session.clone());
+        save_to_file(SESSIONS_FILE, &sessions);
+    } else if let Err(err) = sync_storage::record_session(session) {
+        eprintln!("Failed to save session: {}", err);
+    }
 }
 
 /// Deletes all sessions belonging to a specific game.
 /// Returns `true` if something was deleted.
-pub fn delete_sessions_for_game(game_id: u32) -> bool {
-    let mut sessions = load_from_file::<Session>(SESSIONS_FILE);
+pub fn delete_sessions_for_game(game_id: GameId) -> bool {
+    let mut sessions = load_all_sessions();
     let initial_len = sessions.len();
     sessions.retain(|s| s.game_id != game_id);
 
     if sessions.len() != initial_len {
-        save_to_file(SESSIONS_FILE, &sessions);
+        save_all_sessions(&sessions);
         true
     } else {
         false
     }
 }
+
+/// Sets note and progress of a session.
+pub fn update_session_note(session_id: SessionId, note: &str, progress: &str) {
+    let mut sessions = load_all_sessions();
+
+    if let Some(session) = sessions.iter_mut().find(|s| s.id == session_id) {
+        session.note = note.to_string();
+        session.progress = progress.to_string();
+        save_all_sessions(&sessions);
+    }
+}
+
+/// Ids `migrate_legacy_ids` replaced, so references kept elsewhere can be updated too.
+#[derive(Serialize, Deserialize, Default)]
+pub struct IdChanges {
+    pub games: HashMap<GameId, GameId>,
+    pub categories: HashMap<CategoryId, CategoryId>,
+}
+
+impl IdChanges {
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty() && self.categories.is_empty()
+    }
+}
+
+/// New ids of a migration in progress. Kept until every file is written, so a migration cut
+/// short gives the files it didn't reach the same ids as the ones it did.
+const ID_MIGRATION_FILE: &str = "id-migration.json";
+
+/// Replaces the numeric ids of games, categories, goals and reminders of older versions with
+/// random ones and gives sessions without an id their own. Sessions, goals and reminders follow
+/// their game's new id, games their category's.
+///
+/// Each file is checked for old ids on its own, so a migration that was interrupted finishes
+/// on the next start.
+pub fn migrate_legacy_ids() -> Result<IdChanges, String> {
+    let mut games = load_games();
+    let mut sessions = load_all_sessions();
+    let mut categories = load_categories();
+    let mut goals = load_goals();
+    let mut limits = load_limits();
+
+    let mut changes: IdChanges =
+        fs::read(ID_MIGRATION_FILE).ok().and_then(|bytes| serde_json::from_slice(&bytes).ok()).unwrap_or_default();
+    for game in games.iter().filter(|g| g.id.is_legacy()) {
+        changes.games.entry(game.id).or_insert_with(GameId::random);
+    }
+    for category in categories.iter().filter(|c| c.id.is_legacy()) {
+        changes.categories.entry(category.id).or_insert_with(CategoryId::random);
+    }
+
+    let games_changed =
+        games.iter().any(|g| g.id.is_legacy() || g.category_id.is_some_and(|id| changes.categories.contains_key(&id)));
+    let sessions_changed = sessions.iter().any(|s| s.id.is_nil() || changes.games.contains_key(&s.game_id));
+    let categories_changed = categories.iter().any(|c| c.id.is_legacy());
+    let goals_changed =
+        goals.iter().any(|g| g.id.is_legacy() || g.game_id.is_some_and(|id| changes.games.contains_key(&id)));
+    let limits_changed =
+        limits.iter().any(|l| l.id.is_legacy() || l.game_id.is_some_and(|id| changes.games.contains_key(&id)));
+    if !(games_changed || sessions_changed || categories_changed || goals_changed || limits_changed) {
+        let _ = fs::remove_file(ID_MIGRATION_FILE);
+        return Ok(changes);
+    }
+
+    if !changes.is_empty() {
+        let journal = serde_json::to_vec(&changes).map_err(|e| e.to_string())?;
+        fs::write(ID_MIGRATION_FILE, journal).map_err(|e| format!("Can't write {}: {}", ID_MIGRATION_FILE, e))?;
+    }
+
+    let remap = |id: &mut GameId| {
+        if let Some(new_id) = changes.games.get(id) {
+            *id = *new_id;
+        }
+    };
+    if games_changed || sessions_changed {
+        for game in &mut games {
+            remap(&mut game.id);
+            game.category_id = game.category_id.map(|id| changes.categories.get(&id).copied().unwrap_or(id));
+        }
+        for session in &mut sessions {
+            remap(&mut session.game_id);
+            if session.id.is_nil() {
+                session.id = SessionId::random();
+            }
+        }
+        save_games_and_sessions(&games, &sessions)?;
+    }
+    if categories_changed {
+        for category in &mut categories {
+            category.id = changes.categories.get(&category.id).copied().unwrap_or(category.id);
+        }
+        save_categories(&categories);
+    }
+    if goals_changed {
+        for goal in &mut goals {
+            goal.game_id.iter_mut().for_each(remap);
+            if goal.id.is_legacy() {
+                goal.id = GoalId::random();
+            }
+        }
+        save_goals(&goals);
+    }
+    if limits_changed {
+        for limit in &mut limits {
+            limit.game_id.iter_mut().for_each(remap);
+            if limit.id.is_legacy() {
+                limit.id = LimitId::random();
+            }
+        }
+        save_limits(&limits);
+    }
+
+    let _ = fs::remove_file(ID_MIGRATION_FILE);
+    Ok(changes)
+}
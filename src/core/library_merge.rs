@@ -0,0 +1,354 @@
+use crate::core::backup::read_backup;
+use crate::core::covers::import_cover;
+use crate::core::duration::{format_date_time, format_duration};
+use crate::core::json_storage::{
+    Category, CategoryId, Game, GameId, Session, SessionId, StatusChange, load_all_sessions, load_categories,
+    load_games, save_categories, save_games_and_sessions,
+};
+use crate::core::sync_storage::{LOGS_DIR, merged_sessions};
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Games, sessions and covers of another tracker, e.g. from a second machine.
+#[derive(Default)]
+pub struct ForeignLibrary {
+    /// File the library was read from.
+    pub source: String,
+    pub games: Vec<Game>,
+    pub sessions: Vec<Session>,
+    pub categories: Vec<Category>,
+    /// Cover images by the path stored in the foreign games.
+    pub covers: HashMap<String, Vec<u8>>,
+}
+
+/// Reads a JSON list next to `dir`. A missing file is an empty list.
+fn read_sibling<T: DeserializeOwned>(dir: &Path, name: &str) -> Result<Vec<T>, String> {
+    let path = dir.join(name);
+    match fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| format!("{} is damaged: {}", path.display(), e)),
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+/// Reads another library from a backup archive (`.zip`) or a `games.json` with `sessions.json`
+/// or device session logs (and optionally `categories.json` and the covers folder) next to it.
+pub fn load_foreign_library(path: &Path) -> Result<ForeignLibrary, String> {
+    let source = path.display().to_string();
+
+    if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("zip")) {
+        let backup = read_backup(path)?;
+        return Ok(ForeignLibrary {
+            source,
+            games: backup.games,
+            sessions: backup.sessions,
+            categories: backup.categories,
+            covers: backup.covers,
+        });
+    }
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let bytes = fs::read(path).map_err(|e| format!("Can't read {}: {}", source, e))?;
+    let games: Vec<Game> = serde_json::from_slice(&bytes).map_err(|e| format!("Not a games file: {}", e))?;
+    let logs = dir.join(LOGS_DIR);
+    let sessions = if logs.is_dir() { merged_sessions(&logs) } else { read_sibling(dir, "sessions.json")? };
+    if sessions.is_empty() && !dir.join("sessions.json").is_file() && !logs.is_dir() {
+        return Err(format!("No sessions.json next to {}", source));
+    }
+
+    let covers = games
+        .iter()
+        .filter(|g| !g.cover.is_empty())
+        .filter_map(|g| fs::read(dir.join(&g.cover)).ok().map(|bytes| (g.cover.clone(), bytes)))
+        .collect();
+
+    Ok(ForeignLibrary { source, games, sessions, categories: read_sibling(dir, "categories.json")?, covers })
+}
+
+/// Why a foreign game was matched to a library game.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MatchReason {
+    Id,
+    Path,
+    Name,
+}
+
+impl MatchReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MatchReason::Id => "same game",
+            MatchReason::Path => "same path",
+            MatchReason::Name => "same name",
+        }
+    }
+}
+
+/// Where the sessions of a foreign game go.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MergeTarget {
+    /// Into this library game.
+    Existing(GameId),
+    /// Into a new library game copied from the foreign one.
+    New,
+    /// Nowhere, the game and its sessions are left out.
+    Skip,
+}
+
+/// Game detail that differs between a foreign game and the library game it merges into.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameField {
+    Name,
+    Path,
+    Status,
+    Notes,
+}
+
+impl GameField {
+    pub const ALL: [GameField; 4] = [GameField::Name, GameField::Path, GameField::Status, GameField::Notes];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameField::Name => "Name",
+            GameField::Path => "Path",
+            GameField::Status => "Status",
+            GameField::Notes => "Notes",
+        }
+    }
+
+    /// The field's value as shown in the review.
+    pub fn value(&self, game: &Game) -> String {
+        match self {
+            GameField::Name => game.name.clone(),
+            GameField::Path => game.path.clone(),
+            GameField::Status => game.status.label().to_string(),
+            GameField::Notes => game.notes.clone(),
+        }
+    }
+
+    /// Copies the field from `from` into `to`.
+    fn copy(&self, from: &Game, to: &mut Game) {
+        match self {
+            GameField::Name => to.name = from.name.clone(),
+            GameField::Path => to.path = from.path.clone(),
+            GameField::Status => {
+                to.status = from.status;
+                to.status_history = merged_history(&to.status_history, &from.status_history);
+            }
+            GameField::Notes => to.notes = from.notes.clone(),
+        }
+    }
+}
+
+/// Both status histories without repeats, oldest first.
+pub fn merged_history(a: &[StatusChange], b: &[StatusChange]) -> Vec<StatusChange> {
+    let mut history: Vec<StatusChange> = a.to_vec();
+    for change in b {
+        if !history.iter().any(|c| c.status == change.status && c.date == change.date) {
+            history.push(change.clone());
+        }
+    }
+    history.sort_by(|x, y| x.date.cmp(&y.date));
+    history
+}
+
+/// A differing detail and which side wins.
+#[derive(Clone)]
+pub struct FieldConflict {
+    pub field: GameField,
+    /// Take the foreign value instead of keeping the library one.
+    pub use_foreign: bool,
+}
+
+/// Decision for one foreign game, reviewed before the merge is written.
+#[derive(Clone)]
+pub struct GameMerge {
+    /// Id of the game in the foreign library.
+    pub foreign_id: GameId,
+    pub target: MergeTarget,
+    /// How the target was found, `None` if nothing matched.
+    pub matched_by: Option<MatchReason>,
+    pub conflicts: Vec<FieldConflict>,
+}
+
+/// Details that differ between two games. The library value is kept by default.
+pub fn find_conflicts(local: &Game, foreign: &Game) -> Vec<FieldConflict> {
+    GameField::ALL
+        .into_iter()
+        .filter(|field| {
+            let foreign_value = field.value(foreign);
+            !foreign_value.trim().is_empty() && field.value(local) != foreign_value
+        })
+        .map(|field| FieldConflict { field, use_foreign: false })
+        .collect()
+}
+
+/// Library game for a foreign one, matched by id first (the game was merged or restored before),
+/// then by path, then by name ignoring case. Numeric ids of older versions are never matched,
+/// every machine counted them from 1.
+pub fn match_game(local_games: &[Game], foreign: &Game) -> Option<(GameId, MatchReason)> {
+    let by_id = local_games.iter().find(|g| !foreign.id.is_legacy() && g.id == foreign.id);
+    let by_path = || local_games.iter().find(|g| !foreign.path.trim().is_empty() && g.path == foreign.path);
+    let by_name = || local_games.iter().find(|g| g.name.trim().eq_ignore_ascii_case(foreign.name.trim()));
+
+    by_id
+        .map(|g| (g.id, MatchReason::Id))
+        .or_else(|| by_path().map(|g| (g.id, MatchReason::Path)))
+        .or_else(|| by_name().map(|g| (g.id, MatchReason::Name)))
+}
+
+/// Proposes a target for every foreign game: the matching library game, or a new one.
+pub fn plan_library_merge(local_games: &[Game], foreign: &ForeignLibrary) -> Vec<GameMerge> {
+    foreign
+        .games
+        .iter()
+        .map(|game| match match_game(local_games, game) {
+            Some((id, reason)) => GameMerge {
+                foreign_id: game.id,
+                target: MergeTarget::Existing(id),
+                matched_by: Some(reason),
+                conflicts: local_games
+                    .iter()
+                    .find(|g| g.id == id)
+                    .map_or(Vec::new(), |local| find_conflicts(local, game)),
+            },
+            None => {
+                GameMerge { foreign_id: game.id, target: MergeTarget::New, matched_by: None, conflicts: Vec::new() }
+            }
+        })
+        .collect()
+}
+
+/// Changes `target` of a reviewed game, recomputing its conflicts.
+pub fn retarget(merge: &mut GameMerge, target: MergeTarget, local_games: &[Game], foreign: &ForeignLibrary) {
+    merge.target = target;
+    merge.conflicts = match (target, foreign.games.iter().find(|g| g.id == merge.foreign_id)) {
+        (MergeTarget::Existing(id), Some(game)) => {
+            local_games.iter().find(|g| g.id == id).map_or(Vec::new(), |local| find_conflicts(local, game))
+        }
+        _ => Vec::new(),
+    };
+}
+
+/// Whether `local` is `foreign` already merged into the library game `game_id`:
+/// the same session id, or the same game and end time.
+fn is_same_session(local: &Session, foreign: &Session, game_id: GameId) -> bool {
+    (!foreign.id.is_nil() && local.id == foreign.id) || (local.game_id == game_id && local.date == foreign.date)
+}
+
+/// Sessions of a foreign game that would be added and those already in the library.
+pub fn session_counts(merge: &GameMerge, local_sessions: &[Session], foreign: &ForeignLibrary) -> (usize, usize) {
+    let own = foreign.sessions.iter().filter(|s| s.game_id == merge.foreign_id);
+    match merge.target {
+        MergeTarget::Skip => (0, 0),
+        MergeTarget::New => (own.count(), 0),
+        MergeTarget::Existing(id) => {
+            let (duplicates, new): (Vec<&Session>, Vec<&Session>) =
+                own.partition(|s| local_sessions.iter().any(|l| is_same_session(l, s, id)));
+            (new.len(), duplicates.len())
+        }
+    }
+}
+
+/// Numbers of a written merge, with the library id each merged foreign game ended up as.
+pub struct MergeSummary {
+    pub games_added: usize,
+    pub games_merged: usize,
+    pub sessions_added: usize,
+    pub sessions_skipped: usize,
+    pub game_ids: HashMap<GameId, GameId>,
+}
+
+/// Writes the reviewed merge. New games keep their id, so merging the same library again matches them,
+/// except numeric ids of older versions, which get a random one. Sessions already in the library
+/// (same id, or same game and end time) are skipped.
+pub fn apply_library_merge(foreign: &ForeignLibrary, plan: &[GameMerge]) -> Result<MergeSummary, String> {
+    let mut games = load_games();
+    let mut sessions = load_all_sessions();
+    let mut categories = load_categories();
+    let mut summary = MergeSummary {
+        games_added: 0,
+        games_merged: 0,
+        sessions_added: 0,
+        sessions_skipped: 0,
+        game_ids: HashMap::new(),
+    };
+
+    for merge in plan {
+        let Some(foreign_game) = foreign.games.iter().find(|g| g.id == merge.foreign_id) else {
+            continue;
+        };
+        let foreign_cover = foreign.covers.get(&foreign_game.cover);
+
+        let id = match merge.target {
+            MergeTarget::Skip => continue,
+            MergeTarget::Existing(id) => {
+                let local = games.iter_mut().find(|g| g.id == id).ok_or("Merge target no longer exists")?;
+                for conflict in merge.conflicts.iter().filter(|c| c.use_foreign) {
+                    conflict.field.copy(foreign_game, local);
+                }
+                for tag in &foreign_game.tags {
+                    if !local.tags.contains(tag) {
+                        local.tags.push(tag.clone());
+                    }
+                }
+                if local.cover.is_empty()
+                    && let Some(bytes) = foreign_cover
+                {
+                    local.cover = import_cover(id, bytes).unwrap_or_default();
+                }
+                summary.games_merged += 1;
+                id
+            }
+            MergeTarget::New => {
+                let mut added = foreign_game.clone();
+                if added.id.is_legacy() || games.iter().any(|g| g.id == added.id) {
+                    added.id = GameId::random();
+                }
+                added.sort_index = games.iter().map(|g| g.sort_index + 1).max().unwrap_or(0);
+                added.category_id = foreign_game
+                    .category_id
+                    .and_then(|id| foreign.categories.iter().find(|c| c.id == id))
+                    .map(|category| match categories.iter().find(|c| c.name.eq_ignore_ascii_case(&category.name)) {
+                        Some(existing) => existing.id,
+                        None => {
+                            let id = CategoryId::random();
+                            categories.push(Category { id, name: category.name.clone() });
+                            id
+                        }
+                    });
+                added.cover = foreign_cover.and_then(|bytes| import_cover(added.id, bytes).ok()).unwrap_or_default();
+                let id = added.id;
+                games.push(added);
+                summary.games_added += 1;
+                id
+            }
+        };
+        summary.game_ids.insert(merge.foreign_id, id);
+    }
+
+    let mut touched = HashSet::new();
+    for session in &foreign.sessions {
+        let Some(&game_id) = summary.game_ids.get(&session.game_id) else {
+            continue;
+        };
+        if sessions.iter().any(|s| is_same_session(s, session, game_id)) {
+            summary.sessions_skipped += 1;
+            continue;
+        }
+        let id = if session.id.is_nil() { SessionId::random() } else { session.id };
+        sessions.push(Session { id, game_id, ..session.clone() });
+        touched.insert(game_id);
+        summary.sessions_added += 1;
+    }
+
+    for game in games.iter_mut().filter(|g| touched.contains(&g.id)) {
+        let own = sessions.iter().filter(|s| s.game_id == game.id);
+        game.hours = format_duration(own.clone().map(|s| s.duration_secs()).sum());
+        game.last_played = own.filter_map(|s| s.end_time()).max().map(format_date_time).unwrap_or_default();
+    }
+
+    save_games_and_sessions(&games, &sessions)?;
+    save_categories(&categories);
+    Ok(summary)
+}
@@ -0,0 +1,144 @@
+use crate::core::json_storage::{Game, GameId, load_games, save_games};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Returns ids of games whose executable no longer exists.
+/// Games without a path, like ones created by a CSV import, were never linked and aren't missing.
+pub fn find_missing_games(games: &[Game]) -> HashSet<GameId> {
+    games.iter().filter(|g| !g.path.trim().is_empty() && !Path::new(&g.path).is_file()).map(|g| g.id).collect()
+}
+
+/// Sets a new executable path for one game.
+pub fn relink_game(game_id: GameId, new_path: String) {
+    let mut games = load_games();
+
+    if let Some(game) = games.iter_mut().find(|g| g.id == game_id) {
+        game.path = new_path;
+    }
+
+    save_games(&games);
+}
+
+/// Checks whether `path` lies under `prefix`, matching whole path components only.
+fn has_path_prefix(path: &str, prefix: &str) -> bool {
+    match path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with(['/', '\\']) || prefix.ends_with(['/', '\\']),
+        None => false,
+    }
+}
+
+/// Counts games whose path starts with `old_prefix`.
+pub fn count_prefix_matches(games: &[Game], old_prefix: &str) -> usize {
+    if old_prefix.is_empty() {
+        return 0;
+    }
+    games.iter().filter(|g| has_path_prefix(&g.path, old_prefix)).count()
+}
+
+/// Rewrites `old_prefix` to `new_prefix` in the paths of all games.
+/// Returns the number of relinked games.
+pub fn relink_path_prefix(old_prefix: &str, new_prefix: &str) -> usize {
+    if old_prefix.is_empty() {
+        return 0;
+    }
+
+    let mut games = load_games();
+    let mut relinked = 0;
+
+    for game in games.iter_mut().filter(|g| has_path_prefix(&g.path, old_prefix)) {
+        game.path = format!("{}{}", new_prefix, &game.path[old_prefix.len()..]);
+        relinked += 1;
+    }
+
+    if relinked > 0 {
+        save_games(&games);
+    }
+    relinked
+}
+
+/// Whether a path prefix names only the root of a file system, like `/` (split to an empty
+/// prefix) or `D:`, and so would match every path on it.
+fn is_root(prefix: &str) -> bool {
+    let folder = prefix.trim_end_matches(['/', '\\']);
+    folder.is_empty() || (folder.len() == 2 && folder.ends_with(':'))
+}
+
+/// Derives the moved folder from an old and a new location of the same file.
+///
+/// For `D:\Games\VN\Game\game.exe` → `E:\Library\VN\Game\game.exe` returns `("D:\Games", "E:\Library")`.
+/// Returns `None` if the file names differ, nothing but the file name is shared, or the old
+/// folder is a whole drive or the root, which would relink every game on it.
+pub fn moved_prefix(old_path: &str, new_path: &str) -> Option<(String, String)> {
+    let old_parts: Vec<&str> = old_path.split(['/', '\\']).collect();
+    let new_parts: Vec<&str> = new_path.split(['/', '\\']).collect();
+
+    let common = old_parts.iter().rev().zip(new_parts.iter().rev()).take_while(|(a, b)| a == b).count();
+    if common == 0 || common >= old_parts.len() || common >= new_parts.len() {
+        return None;
+    }
+
+    // Length of the leading components plus the separators between them
+    let prefix_len = |parts: &[&str]| parts.iter().map(|p| p.len() + 1).sum::<usize>() - 1;
+    let old_prefix = &old_path[..prefix_len(&old_parts[..old_parts.len() - common])];
+    let new_prefix = &new_path[..prefix_len(&new_parts[..new_parts.len() - common])];
+    if is_root(old_prefix) {
+        return None;
+    }
+
+    Some((old_prefix.to_string(), new_prefix.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::json_storage::{LaunchSettings, PlayStatus};
+
+    fn game(path: &str) -> Game {
+        Game {
+            id: GameId::random(),
+            name: "Saya no Uta".to_string(),
+            path: path.to_string(),
+            hours: "0h 0m 0s".to_string(),
+            category_id: None,
+            sort_index: 0,
+            added: String::new(),
+            last_played: String::new(),
+            tags: Vec::new(),
+            status: PlayStatus::Planned,
+            status_history: Vec::new(),
+            launch: LaunchSettings::default(),
+            notes: String::new(),
+            cover: String::new(),
+        }
+    }
+
+    #[test]
+    fn games_without_a_path_are_not_missing() {
+        let here = std::env::current_exe().unwrap().to_string_lossy().into_owned();
+        let games = [game(""), game("  "), game(&here), game("/no/such/vn/game.exe")];
+
+        assert_eq!(find_missing_games(&games), HashSet::from([games[3].id]));
+    }
+
+    #[test]
+    fn moved_folder_is_found_from_both_locations() {
+        assert_eq!(
+            moved_prefix(r"D:\Games\VN\Game\game.exe", r"E:\Library\VN\Game\game.exe"),
+            Some((r"D:\Games".to_string(), r"E:\Library".to_string()))
+        );
+        assert_eq!(
+            moved_prefix("/home/ayu/vn/saya/saya.exe", "/mnt/data/vn/saya/saya.exe"),
+            Some(("/home/ayu".to_string(), "/mnt/data".to_string()))
+        );
+    }
+
+    #[test]
+    fn roots_and_unrelated_files_give_no_prefix() {
+        // Old folder would be the root or a whole drive
+        assert_eq!(moved_prefix("/vn/saya/saya.exe", "/mnt/vn/saya/saya.exe"), None);
+        assert_eq!(moved_prefix(r"D:\VN\Game\game.exe", r"E:\Library\VN\Game\game.exe"), None);
+        assert_eq!(moved_prefix(r"D:\VN\Game\game.exe", r"\\nas\share\VN\Game\game.exe"), None);
+        assert_eq!(moved_prefix("saya.exe", "/vn/saya.exe"), None);
+        assert_eq!(moved_prefix("/vn/saya.exe", "/vn/other.exe"), None);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::core::duration::format_duration;
+use crate::core::json_storage::{GameId, LimitId, LimitKind, PlayLimit, load_limits, save_limits};
+
+const APP_NAME: &str = "VN Time Tracker";
+
+/// Adds a new reminder and returns its id.
+pub fn add_limit(game_id: Option<GameId>, kind: LimitKind, minutes: u64) -> LimitId {
+    let mut limits = load_limits();
+
+    let new_id = LimitId::random();
+    limits.push(PlayLimit { id: new_id, game_id, kind, minutes });
+    save_limits(&limits);
+
+    new_id
+}
+
+/// Saves changed game, kind or interval of an existing reminder.
+pub fn update_limit(limit: &PlayLimit) {
+    let mut limits = load_limits();
+
+    if let Some(existing) = limits.iter_mut().find(|l| l.id == limit.id) {
+        *existing = limit.clone();
+    }
+
+    save_limits(&limits);
+}
+
+/// Removes a reminder.
+pub fn delete_limit(limit_id: LimitId) {
+    let mut limits = load_limits();
+    limits.retain(|l| l.id != limit_id);
+    save_limits(&limits);
+}
+
+/// Removes the reminders of a deleted game.
+pub fn delete_limits_for_game(game_id: GameId) {
+    let mut limits = load_limits();
+    limits.retain(|l| l.game_id != Some(game_id));
+    save_limits(&limits);
+}
+
+/// Points the reminders of a merged game at the game it was merged into.
+pub fn reassign_limits(from_game_id: GameId, to_game_id: GameId) {
+    let mut limits = load_limits();
+
+    for limit in limits.iter_mut().filter(|l| l.game_id == Some(from_game_id)) {
+        limit.game_id = Some(to_game_id);
+    }
+
+    save_limits(&limits);
+}
+
+/// Reminders of `game_id` whose moment passed while the session went from `previous_secs` to `now_secs`.
+pub fn due_reminders(limits: &[PlayLimit], game_id: GameId, previous_secs: u64, now_secs: u64) -> Vec<PlayLimit> {
+    limits
+        .iter()
+        .filter(|l| l.game_id.is_none_or(|id| id == game_id) && l.minutes > 0)
+        .filter(|l| {
+            let interval = l.minutes * 60;
+            match l.kind {
+                LimitKind::After => previous_secs < interval && interval <= now_secs,
+                LimitKind::Every => previous_secs / interval < now_secs / interval,
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+/// Text shown when a reminder fires.
+pub fn reminder_message(limit: &PlayLimit, game_name: &str, elapsed_secs: u64) -> String {
+    match limit.kind {
+        LimitKind::After => format!("You've been playing {} for {}", game_name, format_duration(elapsed_secs)),
+        LimitKind::Every => {
+            format!("Time for a break! {} has been running for {}", game_name, format_duration(elapsed_secs))
+        }
+    }
+}
+
+/// Arguments of the freedesktop `org.freedesktop.Notifications.Notify` method.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NotifyCall {
+    pub app_name: String,
+    pub replaces_id: u32,
+    pub app_icon: String,
+    pub summary: String,
+    pub body: String,
+    pub actions: Vec<String>,
+    /// Milliseconds, `-1` lets the notification server decide.
+    pub expire_timeout: i32,
+}
+
+impl NotifyCall {
+    pub fn new(summary: &str, body: &str) -> Self {
+        Self {
+            app_name: APP_NAME.to_string(),
+            replaces_id: 0,
+            app_icon: String::new(),
+            summary: summary.to_string(),
+            body: body.to_string(),
+            actions: Vec::new(),
+            expire_timeout: -1,
+        }
+    }
+}
+
+/// Connection to a desktop notification service. Replace with a mock to check what would be sent.
+pub trait NotificationBus {
+    /// Shows a notification and returns its id.
+    fn notify(&self, call: &NotifyCall) -> Result<u32, String>;
+}
+
+/// The freedesktop notification service on the D-Bus session bus.
+pub struct SessionBus;
+
+#[cfg(target_os = "linux")]
+impl NotificationBus for SessionBus {
+    fn notify(&self, call: &NotifyCall) -> Result<u32, String> {
+        use std::collections::HashMap;
+        use zbus::zvariant::Value;
+
+        let connection = zbus::blocking::Connection::session().map_err(|e| e.to_string())?;
+        let hints: HashMap<&str, Value> = HashMap::new();
+        let reply = connection
+            .call_method(
+                Some("org.freedesktop.Notifications"),
+                "/org/freedesktop/Notifications",
+                Some("org.freedesktop.Notifications"),
+                "Notify",
+                &(
+                    &call.app_name,
+                    call.replaces_id,
+                    &call.app_icon,
+                    &call.summary,
+                    &call.body,
+                    &call.actions,
+                    hints,
+                    call.expire_timeout,
+                ),
+            )
+            .map_err(|e| e.to_string())?;
+
+        reply.body().deserialize().map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl NotificationBus for SessionBus {
+    fn notify(&self, _call: &NotifyCall) -> Result<u32, String> {
+        Err("Desktop notifications are only supported on Linux".to_string())
+    }
+}
+
+/// Follows one running session and fires its reminders.
+pub struct ReminderTracker {
+    game_id: GameId,
+    game_name: String,
+    limits: Vec<PlayLimit>,
+    checked_secs: u64,
+}
+
+impl ReminderTracker {
+    pub fn new(game_id: GameId, game_name: String, limits: Vec<PlayLimit>) -> Self {
+        Self { game_id, game_name, limits, checked_secs: 0 }
+    }
+
+    /// Sends a desktop notification for every reminder due since the last check
+    /// and returns their messages for the in-app notification.
+    pub fn tick(&mut self, elapsed_secs: u64, bus: &dyn NotificationBus) -> Vec<String> {
+        let due = due_reminders(&self.limits, self.game_id, self.checked_secs, elapsed_secs);
+        self.checked_secs = elapsed_secs;
+
+        due.iter()
+            .map(|limit| {
+                let message = reminder_message(limit, &self.game_name, elapsed_secs);
+                if let Err(err) = bus.notify(&NotifyCall::new("Break reminder", &message)) {
+                    eprintln!("Failed to show desktop notification: {}", err);
+                }
+                message
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Bus that keeps every notification instead of showing it.
+    #[derive(Default)]
+    struct RecordingBus {
+        calls: RefCell<Vec<NotifyCall>>,
+    }
+
+    impl NotificationBus for RecordingBus {
+        fn notify(&self, call: &NotifyCall) -> Result<u32, String> {
+            let mut calls = self.calls.borrow_mut();
+            calls.push(call.clone());
+            Ok(calls.len() as u32)
+        }
+    }
+
+    fn limit(game_id: Option<GameId>, kind: LimitKind, minutes: u64) -> PlayLimit {
+        PlayLimit { id: LimitId::random(), game_id, kind, minutes }
+    }
+
+    /// Ticks once a second up to `until_secs` and returns the elapsed seconds each notification was sent at.
+    fn run(tracker: &mut ReminderTracker, bus: &RecordingBus, until_secs: u64) -> Vec<u64> {
+        let mut fired = Vec::new();
+        for elapsed in 0..=until_secs {
+            let before = bus.calls.borrow().len();
+            let messages = tracker.tick(elapsed, bus);
+            assert_eq!(messages.len(), bus.calls.borrow().len() - before);
+            fired.extend(messages.iter().map(|_| elapsed));
+        }
+        fired
+    }
+
+    #[test]
+    fn after_reminder_fires_once() {
+        let game = GameId::random();
+        let bus = RecordingBus::default();
+        let mut tracker = ReminderTracker::new(game, "Saya".to_string(), vec![limit(Some(game), LimitKind::After, 2)]);
+
+        assert_eq!(run(&mut tracker, &bus, 600), vec![120]);
+
+        let calls = bus.calls.borrow();
+        assert_eq!(calls[0].summary, "Break reminder");
+        assert_eq!(calls[0].body, "You've been playing Saya for 0h 2m 0s");
+        assert_eq!(calls[0].app_name, APP_NAME);
+    }
+
+    #[test]
+    fn every_reminder_fires_once_per_interval() {
+        let game = GameId::random();
+        let bus = RecordingBus::default();
+        let mut tracker = ReminderTracker::new(game, "Saya".to_string(), vec![limit(None, LimitKind::Every, 1)]);
+
+        assert_eq!(run(&mut tracker, &bus, 200), vec![60, 120, 180]);
+        assert_eq!(bus.calls.borrow()[1].body, "Time for a break! Saya has been running for 0h 2m 0s");
+    }
+
+    #[test]
+    fn skipped_ticks_fire_each_reminder_once() {
+        let game = GameId::random();
+        let bus = RecordingBus::default();
+        let limits = vec![limit(Some(game), LimitKind::After, 1), limit(Some(game), LimitKind::Every, 1)];
+        let mut tracker = ReminderTracker::new(game, "Saya".to_string(), limits);
+
+        // The waiting thread woke up late and jumped over several intervals
+        assert_eq!(tracker.tick(30, &bus).len(), 0);
+        assert_eq!(tracker.tick(250, &bus).len(), 2);
+        assert_eq!(tracker.tick(250, &bus).len(), 0);
+        assert_eq!(tracker.tick(299, &bus).len(), 0);
+        assert_eq!(tracker.tick(300, &bus).len(), 1);
+        assert_eq!(bus.calls.borrow().len(), 3);
+    }
+
+    #[test]
+    fn reminders_of_other_games_and_disabled_ones_are_ignored() {
+        let game = GameId::random();
+        let bus = RecordingBus::default();
+        let limits = vec![limit(Some(GameId::random()), LimitKind::After, 1), limit(None, LimitKind::Every, 0)];
+        let mut tracker = ReminderTracker::new(game, "Saya".to_string(), limits);
+
+        assert!(run(&mut tracker, &bus, 300).is_empty());
+        assert!(bus.calls.borrow().is_empty());
+    }
+}
@@ -0,0 +1,22 @@
+//! Temporary folders for tests that read and write files.
+
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// An empty folder of its own for one test, removed when dropped.
+pub struct ScratchDir(pub PathBuf);
+
+impl ScratchDir {
+    pub fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("vn-time-tracker-{}", Uuid::new_v4().simple()));
+        fs::create_dir_all(&dir).unwrap();
+        ScratchDir(dir)
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
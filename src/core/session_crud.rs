@@ -0,0 +1,261 @@
+use crate::core::duration::{format_date_time, format_duration, parse_date_time};
+use crate::core::json_storage::{
+    Game, GameId, Session, SessionId, load_all_sessions, load_games, save_all_sessions, save_games,
+};
+use chrono::NaiveDateTime;
+
+/// Longest break between two sessions that can still be merged, e.g. to restart a crashed game.
+pub const MAX_MERGE_GAP_SECS: i64 = 10 * 60;
+
+/// Returns `(start, end)` of a session, or an error if its date can't be parsed.
+fn session_interval(session: &Session) -> Result<(NaiveDateTime, NaiveDateTime), String> {
+    match (session.start_time(), session.end_time()) {
+        (Some(start), Some(end)) => Ok((start, end)),
+        _ => Err(format!("Session date '{}' has invalid format", session.date)),
+    }
+}
+
+/// Finds the index of a session.
+fn find_session(sessions: &[Session], session: &Session) -> Result<usize, String> {
+    sessions.iter().position(|s| s.id == session.id).ok_or_else(|| format!("Session '{}' not found", session.date))
+}
+
+/// Checks whether two half-open intervals `[start, end)` intersect.
+pub fn intervals_overlap(a: (NaiveDateTime, NaiveDateTime), b: (NaiveDateTime, NaiveDateTime)) -> bool {
+    a.0 < b.1 && b.0 < a.1
+}
+
+/// Returns the end of the latest session among the given ones, or an empty string.
+fn latest_session_end<'a>(sessions: impl Iterator<Item = &'a Session>) -> String {
+    sessions.filter_map(|s| s.end_time()).max().map(format_date_time).unwrap_or_default()
+}
+
+/// Recomputes the total played time and last played date of a game from its sessions.
+pub fn recalculate_game_totals(game_id: GameId) {
+    let sessions = load_all_sessions();
+    let mut games = load_games();
+    if let Some(game) = games.iter_mut().find(|g| g.id == game_id) {
+        set_game_totals(game, &sessions);
+        save_games(&games);
+    }
+}
+
+/// Sets the total played time and last played date of a game from `sessions`, which may
+/// include other games' ones.
+pub fn set_game_totals(game: &mut Game, sessions: &[Session]) {
+    let total: u64 = sessions.iter().filter(|s| s.game_id == game.id).map(|s| s.duration_secs()).sum();
+    game.hours = format_duration(total);
+    game.last_played = latest_session_end(sessions.iter().filter(|s| s.game_id == game.id));
+}
+
+/// Fills `last_played` of games stored before it was tracked.
+/// Returns `true` if any game was updated.
+pub fn backfill_last_played() -> bool {
+    let mut games = load_games();
+    if games.iter().all(|g| !g.last_played.is_empty()) {
+        return false;
+    }
+
+    let sessions = load_all_sessions();
+    let mut changed = false;
+    for game in games.iter_mut().filter(|g| g.last_played.is_empty()) {
+        game.last_played = latest_session_end(sessions.iter().filter(|s| s.game_id == game.id));
+        changed |= !game.last_played.is_empty();
+    }
+
+    if changed {
+        save_games(&games);
+    }
+    changed
+}
+
+/// Splits a session into two parts at the given moment (`"%Y-%m-%d %H:%M:%S"`).
+///
+/// The note and progress stay with the later part, since they describe where the player stopped.
+pub fn split_session(session: &Session, at: &str) -> Result<(), String> {
+    let mut sessions = load_all_sessions();
+    let index = find_session(&sessions, session)?;
+    let (start, end) = session_interval(&sessions[index])?;
+
+    let at = parse_date_time(at).ok_or_else(|| format!("'{}' is not a valid time", at))?;
+    if at <= start || at >= end {
+        return Err(format!("Split time must be between {} and {}", format_date_time(start), format_date_time(end)));
+    }
+
+    let first = Session {
+        id: SessionId::random(),
+        game_id: session.game_id,
+        date: format_date_time(at),
+        duration: format_duration((at - start).num_seconds() as u64),
+        note: String::new(),
+        progress: String::new(),
+    };
+
+    let second = &mut sessions[index];
+    second.duration = format_duration((end - at).num_seconds() as u64);
+
+    sessions.insert(index, first);
+    save_all_sessions(&sessions);
+    Ok(())
+}
+
+/// Merges two sessions of the same game into one session from the start of the earlier
+/// to the end of the later one.
+///
+/// Fails if the sessions belong to different games, if more than [`MAX_MERGE_GAP_SECS`]
+/// lie between them (the gap would count as playtime), or if the merged session would
+/// overlap any other recorded session.
+pub fn merge_sessions(first: &Session, second: &Session) -> Result<(), String> {
+    if first.game_id != second.game_id {
+        return Err("Only sessions of the same game can be merged".to_string());
+    }
+
+    let mut sessions = load_all_sessions();
+    merge_in(&mut sessions, first, second)?;
+    save_all_sessions(&sessions);
+    recalculate_game_totals(first.game_id);
+    Ok(())
+}
+
+/// Does the work of [`merge_sessions`] on a loaded session list.
+fn merge_in(sessions: &mut Vec<Session>, first: &Session, second: &Session) -> Result<(), String> {
+    let first_index = find_session(sessions, first)?;
+    let second_index = find_session(sessions, second)?;
+    if first_index == second_index {
+        return Err("Can't merge a session with itself".to_string());
+    }
+
+    let a = session_interval(&sessions[first_index])?;
+    let b = session_interval(&sessions[second_index])?;
+    let (earlier_index, later_index) =
+        if a.0 <= b.0 { (first_index, second_index) } else { (second_index, first_index) };
+    let merged = (a.0.min(b.0), a.1.max(b.1));
+
+    let gap = (a.0.max(b.0) - a.1.min(b.1)).num_seconds();
+    if gap > MAX_MERGE_GAP_SECS {
+        return Err(format!(
+            "The sessions are {} apart, only sessions at most {} minutes apart can be merged",
+            format_duration(gap as u64),
+            MAX_MERGE_GAP_SECS / 60
+        ));
+    }
+
+    // The merged span must not swallow other sessions
+    for (i, other) in sessions.iter().enumerate() {
+        if i == first_index || i == second_index {
+            continue;
+        }
+        if let Ok(interval) = session_interval(other)
+            && intervals_overlap(merged, interval)
+        {
+            return Err(format!("Merged session would overlap the session ended at {}", other.date));
+        }
+    }
+
+    let earlier = sessions[earlier_index].clone();
+    let later = &mut sessions[later_index];
+    later.date = format_date_time(merged.1);
+    later.duration = format_duration((merged.1 - merged.0).num_seconds() as u64);
+    later.note = [earlier.note.as_str(), later.note.as_str()]
+        .iter()
+        .filter(|n| !n.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    if later.progress.is_empty() {
+        later.progress = earlier.progress;
+    }
+
+    sessions.remove(earlier_index);
+    Ok(())
+}
+
+/// Deletes a single session and updates the game total.
+pub fn delete_session(session: &Session) -> Result<(), String> {
+    let mut sessions = load_all_sessions();
+    let index = find_session(&sessions, session)?;
+
+    sessions.remove(index);
+    save_all_sessions(&sessions);
+    recalculate_game_totals(session.game_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(game_id: GameId, end: &str, secs: u64) -> Session {
+        Session {
+            id: SessionId::random(),
+            game_id,
+            date: end.to_string(),
+            duration: format_duration(secs),
+            note: String::new(),
+            progress: String::new(),
+        }
+    }
+
+    #[test]
+    fn merged_session_spans_both() {
+        let game = GameId::random();
+        // 10:00-11:00 and 11:05-12:00
+        let mut first = session(game, "2025-03-10 11:00:00", 3600);
+        first.note = "Common route".to_string();
+        first.progress = "Chapter 2".to_string();
+        let second = session(game, "2025-03-10 12:00:00", 3300);
+        let mut sessions = vec![first.clone(), second.clone()];
+
+        merge_in(&mut sessions, &second, &first).unwrap();
+
+        assert_eq!(sessions.len(), 1);
+        let merged = &sessions[0];
+        assert_eq!(merged.id, second.id);
+        assert_eq!(merged.start_time().map(format_date_time).as_deref(), Some("2025-03-10 10:00:00"));
+        assert_eq!(merged.date, "2025-03-10 12:00:00");
+        assert_eq!(merged.duration, "2h 0m 0s");
+        assert_eq!(merged.note, "Common route");
+        assert_eq!(merged.progress, "Chapter 2");
+    }
+
+    #[test]
+    fn sessions_far_apart_are_not_merged() {
+        let game = GameId::random();
+        let first = session(game, "2025-03-10 11:00:00", 3600);
+        let second = session(game, "2025-03-10 20:00:00", 3600);
+        let mut sessions = vec![first.clone(), second.clone()];
+
+        assert!(merge_in(&mut sessions, &first, &second).is_err());
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].duration, first.duration);
+        assert_eq!(sessions[1].duration, second.duration);
+    }
+
+    #[test]
+    fn merge_checks_the_whole_span_for_other_sessions() {
+        let game = GameId::random();
+        // 10:00-11:00, another game 11:02-11:04, 11:05-12:00
+        let first = session(game, "2025-03-10 11:00:00", 3600);
+        let other = session(GameId::random(), "2025-03-10 11:04:00", 120);
+        let second = session(game, "2025-03-10 12:00:00", 3300);
+        let mut sessions = vec![first.clone(), other, second.clone()];
+
+        assert!(merge_in(&mut sessions, &first, &second).is_err());
+        assert_eq!(sessions.len(), 3);
+    }
+
+    #[test]
+    fn overlapping_sessions_merge_into_their_union() {
+        let game = GameId::random();
+        // 10:00-11:00 and 10:30-11:30
+        let first = session(game, "2025-03-10 11:00:00", 3600);
+        let second = session(game, "2025-03-10 11:30:00", 3600);
+        let mut sessions = vec![first.clone(), second.clone()];
+
+        merge_in(&mut sessions, &first, &second).unwrap();
+
+        assert_eq!(sessions[0].start_time().map(format_date_time).as_deref(), Some("2025-03-10 10:00:00"));
+        assert_eq!(sessions[0].date, "2025-03-10 11:30:00");
+        assert_eq!(sessions[0].duration, "1h 30m 0s");
+    }
+}
@@ -0,0 +1,103 @@
+use crate::core::duration::format_duration;
+use crate::core::json_storage::{Session, load_all_sessions, save_all_sessions};
+use crate::core::session_crud::{intervals_overlap, recalculate_game_totals};
+
+/// Two recorded sessions whose time intervals intersect.
+#[derive(Clone)]
+pub struct SessionOverlap {
+    /// Session that started first.
+    pub earlier: Session,
+    /// Session that started later.
+    pub later: Session,
+    /// Doubly counted time in seconds.
+    pub overlap_secs: u64,
+}
+
+impl SessionOverlap {
+    /// Whether both sessions belong to the same game.
+    pub fn same_game(&self) -> bool {
+        self.earlier.game_id == self.later.game_id
+    }
+
+    /// Stable key used to remember overlaps the user decided to keep.
+    pub fn key(&self) -> String {
+        format!("{}|{}|{}|{}", self.earlier.game_id, self.earlier.date, self.later.game_id, self.later.date)
+    }
+}
+
+/// How an overlap should be resolved.
+#[derive(Clone, Copy)]
+pub enum OverlapResolution {
+    /// Shorten the later session so it starts when the earlier one ends.
+    TrimLater,
+    /// Remove the session that started first.
+    DropEarlier,
+    /// Remove the session that started later.
+    DropLater,
+    /// Leave both sessions as they are.
+    Keep,
+}
+
+/// Finds all pairs of overlapping sessions, both within one game and across games.
+///
+/// Sessions with an unparsable date are skipped.
+pub fn find_overlaps(sessions: &[Session]) -> Vec<SessionOverlap> {
+    let mut timed: Vec<_> = sessions
+        .iter()
+        .filter_map(|s| Some((s.start_time()?, s.end_time()?, s)))
+        .filter(|(start, end, _)| start < end)
+        .collect();
+    timed.sort_by_key(|(start, end, _)| (*start, *end));
+
+    let mut overlaps = Vec::new();
+    for (i, (start_a, end_a, a)) in timed.iter().enumerate() {
+        for (start_b, end_b, b) in &timed[i + 1..] {
+            // Sorted by start, so no later session can overlap `a` either
+            if start_b >= end_a {
+                break;
+            }
+            if intervals_overlap((*start_a, *end_a), (*start_b, *end_b)) {
+                let overlap_secs = ((*end_a).min(*end_b) - *start_b).num_seconds() as u64;
+                overlaps.push(SessionOverlap { earlier: (*a).clone(), later: (*b).clone(), overlap_secs });
+            }
+        }
+    }
+
+    overlaps
+}
+
+/// Applies a resolution to an overlap and recalculates totals of the affected games.
+pub fn resolve_overlap(overlap: &SessionOverlap, resolution: OverlapResolution) -> Result<(), String> {
+    let mut sessions = load_all_sessions();
+    let position = |s: &Session| {
+        sessions.iter().position(|x| x.id == s.id).ok_or_else(|| format!("Session '{}' not found", s.date))
+    };
+    let earlier_index = position(&overlap.earlier)?;
+    let later_index = position(&overlap.later)?;
+
+    match resolution {
+        OverlapResolution::TrimLater => {
+            let later = &mut sessions[later_index];
+            let remaining = later.duration_secs().saturating_sub(overlap.overlap_secs);
+            if remaining == 0 {
+                sessions.remove(later_index);
+            } else {
+                later.duration = format_duration(remaining);
+            }
+        }
+        OverlapResolution::DropEarlier => {
+            sessions.remove(earlier_index);
+        }
+        OverlapResolution::DropLater => {
+            sessions.remove(later_index);
+        }
+        OverlapResolution::Keep => return Ok(()),
+    }
+
+    save_all_sessions(&sessions);
+    recalculate_game_totals(overlap.earlier.game_id);
+    if !overlap.same_game() {
+        recalculate_game_totals(overlap.later.game_id);
+    }
+    Ok(())
+}
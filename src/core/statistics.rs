@@ -0,0 +1,495 @@
+use crate::core::json_storage::{GameId, Session};
+use chrono::{Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Aggregated numbers for a set of sessions.
+#[derive(Clone, Default)]
+pub struct SessionSummary {
+    pub count: usize,
+    pub total_secs: u64,
+    pub average_secs: u64,
+    pub longest_secs: u64,
+    /// Start of the earliest session.
+    pub first_played: Option<NaiveDateTime>,
+    /// End of the latest session.
+    pub last_played: Option<NaiveDateTime>,
+}
+
+/// Summarizes sessions: count, total, average and longest length, first and last play.
+pub fn summarize_sessions(sessions: &[Session]) -> SessionSummary {
+    let total_secs: u64 = sessions.iter().map(|s| s.duration_secs()).sum();
+
+    SessionSummary {
+        count: sessions.len(),
+        total_secs,
+        average_secs: if sessions.is_empty() { 0 } else { total_secs / sessions.len() as u64 },
+        longest_secs: sessions.iter().map(|s| s.duration_secs()).max().unwrap_or(0),
+        first_played: sessions.iter().filter_map(|s| s.start_time()).min(),
+        last_played: sessions.iter().filter_map(|s| s.end_time()).max(),
+    }
+}
+
+/// Sums playtime per calendar month (`"YYYY-MM"`) by session end, oldest month first.
+///
+/// Months without sessions between the first and last one are included with zero.
+pub fn playtime_per_month(sessions: &[Session]) -> Vec<(String, u64)> {
+    let mut months: Vec<(String, u64)> = Vec::new();
+
+    let mut by_end: Vec<_> = sessions.iter().filter_map(|s| Some((s.end_time()?, s.duration_secs()))).collect();
+    by_end.sort_by_key(|(end, _)| *end);
+
+    for (end, secs) in by_end {
+        let month = end.format("%Y-%m").to_string();
+        match months.last_mut() {
+            Some((last, total)) if *last == month => *total += secs,
+            _ => {
+                // Fill the gap since the previous month
+                if let Some((last, _)) = months.last() {
+                    let mut next = next_month(last);
+                    while next < month {
+                        months.push((next.clone(), 0));
+                        next = next_month(&next);
+                    }
+                }
+                months.push((month, secs));
+            }
+        }
+    }
+
+    months
+}
+
+/// Returns the month after `"YYYY-MM"`.
+fn next_month(month: &str) -> String {
+    let (year, month) = month.split_once('-').unwrap_or(("0", "0"));
+    let (year, month): (i32, u32) = (year.parse().unwrap_or(0), month.parse().unwrap_or(0));
+    if month >= 12 { format!("{:04}-01", year + 1) } else { format!("{:04}-{:02}", year, month + 1) }
+}
+
+/// Half-open time range `[start, end)` statistics are computed for.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DateRange {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+impl DateRange {
+    /// Range covering whole days from `first` to `last`, both included.
+    pub fn days(first: NaiveDate, last: NaiveDate) -> Self {
+        let end = last.checked_add_days(Days::new(1)).unwrap_or(NaiveDate::MAX);
+        Self { start: first.and_time(NaiveTime::MIN), end: end.and_time(NaiveTime::MIN) }
+    }
+
+    /// Range containing every possible session.
+    pub fn all_time() -> Self {
+        Self { start: NaiveDateTime::MIN, end: NaiveDateTime::MAX }
+    }
+
+    /// Seconds of `[start, end)` that fall into this range.
+    pub fn overlap_secs(&self, start: NaiveDateTime, end: NaiveDateTime) -> u64 {
+        let from = start.max(self.start);
+        let to = end.min(self.end);
+        if to > from { (to - from).num_seconds() as u64 } else { 0 }
+    }
+}
+
+/// Predefined periods of the statistics window.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Period {
+    Today,
+    #[default]
+    ThisWeek,
+    ThisMonth,
+    ThisYear,
+    AllTime,
+}
+
+impl Period {
+    pub const ALL: [Period; 5] =
+        [Period::Today, Period::ThisWeek, Period::ThisMonth, Period::ThisYear, Period::AllTime];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Period::Today => "Today",
+            Period::ThisWeek => "This week",
+            Period::ThisMonth => "This month",
+            Period::ThisYear => "This year",
+            Period::AllTime => "All time",
+        }
+    }
+
+    /// The period containing `today`. Weeks start on Monday.
+    pub fn range(&self, today: NaiveDate) -> DateRange {
+        match self {
+            Period::Today => DateRange::days(today, today),
+            Period::ThisWeek => {
+                let monday = today - Days::new(today.weekday().num_days_from_monday() as u64);
+                DateRange::days(monday, monday + Days::new(6))
+            }
+            Period::ThisMonth => {
+                let first = today.with_day(1).unwrap_or(today);
+                let last = first + Months::new(1) - Days::new(1);
+                DateRange::days(first, last)
+            }
+            Period::ThisYear => {
+                let first = NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap_or(today);
+                let last = NaiveDate::from_ymd_opt(today.year(), 12, 31).unwrap_or(today);
+                DateRange::days(first, last)
+            }
+            Period::AllTime => DateRange::all_time(),
+        }
+    }
+}
+
+/// Aggregated numbers for the sessions of a date range.
+#[derive(Clone, Default)]
+pub struct RangeStatistics {
+    /// Playtime inside the range; sessions crossing its bounds count only partially.
+    pub total_secs: u64,
+    /// Sessions that overlap the range.
+    pub session_count: usize,
+    /// Average full length of the overlapping sessions.
+    pub average_secs: u64,
+    pub longest_secs: u64,
+    /// Days with any playtime inside the range.
+    pub active_days: usize,
+    /// Playtime inside the range per game id, most played first.
+    pub per_game: Vec<(GameId, u64)>,
+}
+
+/// Splits `[start, end)` at midnights, keeping only the time inside `range`.
+fn split_by_day(start: NaiveDateTime, end: NaiveDateTime, range: &DateRange) -> Vec<(NaiveDate, u64)> {
+    let start = start.max(range.start);
+    let end = end.min(range.end);
+
+    let mut parts = Vec::new();
+    let mut day = start.date();
+    while start < end && day <= end.date() {
+        let secs = DateRange::days(day, day).overlap_secs(start, end);
+        if secs > 0 {
+            parts.push((day, secs));
+        }
+        let Some(next) = day.succ_opt() else { break };
+        day = next;
+    }
+    parts
+}
+
+/// Sums playtime per day inside `range`, splitting sessions that cross midnight.
+pub fn playtime_per_day(sessions: &[Session], range: &DateRange) -> BTreeMap<NaiveDate, u64> {
+    playtime_per_day_by_game(sessions, range)
+        .into_iter()
+        .map(|(day, games)| (day, games.iter().map(|(_, secs)| secs).sum()))
+        .collect()
+}
+
+/// Like [`playtime_per_day`], but keeps the playtime of each game id, most played first.
+pub fn playtime_per_day_by_game(sessions: &[Session], range: &DateRange) -> BTreeMap<NaiveDate, Vec<(GameId, u64)>> {
+    let mut days: BTreeMap<NaiveDate, HashMap<GameId, u64>> = BTreeMap::new();
+
+    for session in sessions {
+        let (Some(start), Some(end)) = (session.start_time(), session.end_time()) else { continue };
+        for (day, secs) in split_by_day(start, end, range) {
+            *days.entry(day).or_default().entry(session.game_id).or_insert(0) += secs;
+        }
+    }
+
+    days.into_iter()
+        .map(|(day, games)| {
+            let mut games: Vec<(GameId, u64)> = games.into_iter().collect();
+            games.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            (day, games)
+        })
+        .collect()
+}
+
+/// Sums playtime per week inside `range`, keyed by the Monday the week starts on.
+pub fn playtime_per_week(sessions: &[Session], range: &DateRange) -> BTreeMap<NaiveDate, u64> {
+    let mut weeks = BTreeMap::new();
+
+    for (day, secs) in playtime_per_day(sessions, range) {
+        let monday = day - Days::new(day.weekday().num_days_from_monday() as u64);
+        *weeks.entry(monday).or_insert(0) += secs;
+    }
+
+    weeks
+}
+
+/// Total playtime reached at the end of each session, oldest first.
+pub fn cumulative_playtime(sessions: &[Session]) -> Vec<(NaiveDateTime, u64)> {
+    let mut by_end: Vec<_> = sessions.iter().filter_map(|s| Some((s.end_time()?, s.duration_secs()))).collect();
+    by_end.sort_by_key(|(end, _)| *end);
+
+    let mut total = 0;
+    by_end
+        .into_iter()
+        .map(|(end, secs)| {
+            total += secs;
+            (end, total)
+        })
+        .collect()
+}
+
+/// Counts sessions by length. Bucket `i` holds sessions of `[i * bucket_secs, (i + 1) * bucket_secs)`.
+pub fn session_length_histogram(sessions: &[Session], bucket_secs: u64) -> Vec<usize> {
+    let mut buckets = Vec::new();
+
+    for session in sessions {
+        let index = (session.duration_secs() / bucket_secs.max(1)) as usize;
+        if buckets.len() <= index {
+            buckets.resize(index + 1, 0);
+        }
+        buckets[index] += 1;
+    }
+
+    buckets
+}
+
+/// Sessions that overlap `range`.
+pub fn sessions_in_range(sessions: &[Session], range: &DateRange) -> Vec<Session> {
+    sessions
+        .iter()
+        .filter(|s| match (s.start_time(), s.end_time()) {
+            (Some(start), Some(end)) => range.overlap_secs(start, end) > 0,
+            _ => false,
+        })
+        .cloned()
+        .collect()
+}
+
+/// When sessions happen: playtime by weekday (Monday first) and hour of day.
+#[derive(Clone, Default)]
+pub struct TimeDistribution {
+    /// Seconds played per `[weekday][hour]`.
+    pub matrix: [[u64; 24]; 7],
+    /// Seconds played per weekday.
+    pub weekday_totals: [u64; 7],
+    /// Average seconds per calendar day of each weekday, counting days without play
+    /// between the first and the last played day.
+    pub weekday_averages: [u64; 7],
+}
+
+/// Spreads the sessions inside `range` over weekdays and hours, splitting them at hour boundaries.
+pub fn time_distribution(sessions: &[Session], range: &DateRange) -> TimeDistribution {
+    let mut distribution = TimeDistribution::default();
+    let mut played: Option<(NaiveDate, NaiveDate)> = None;
+
+    for session in sessions {
+        let (Some(start), Some(end)) = (session.start_time(), session.end_time()) else { continue };
+        let mut time = start.max(range.start);
+        let end = end.min(range.end);
+        if time >= end {
+            continue;
+        }
+
+        played = Some(match played {
+            Some((first, last)) => (first.min(time.date()), last.max(end.date())),
+            None => (time.date(), end.date()),
+        });
+
+        while time < end {
+            let hour_start = time.date().and_hms_opt(time.hour(), 0, 0).unwrap_or(time);
+            let next = (hour_start + Duration::hours(1)).min(end);
+            let weekday = time.weekday().num_days_from_monday() as usize;
+            let secs = (next - time).num_seconds() as u64;
+
+            distribution.matrix[weekday][time.hour() as usize] += secs;
+            distribution.weekday_totals[weekday] += secs;
+            time = next;
+        }
+    }
+
+    if let Some((first, last)) = played {
+        let mut day_counts = [0u64; 7];
+        for day in first.iter_days().take_while(|d| *d <= last) {
+            day_counts[day.weekday().num_days_from_monday() as usize] += 1;
+        }
+        let averages = distribution.weekday_averages.iter_mut().zip(distribution.weekday_totals).zip(day_counts);
+        for ((average, total), count) in averages {
+            *average = total.checked_div(count).unwrap_or(0);
+        }
+    }
+
+    distribution
+}
+
+/// Computes totals, session lengths, active days and per-game shares for `range`.
+pub fn range_statistics(sessions: &[Session], range: &DateRange) -> RangeStatistics {
+    let mut stats = RangeStatistics::default();
+    let mut per_game: HashMap<GameId, u64> = HashMap::new();
+    let mut lengths_total = 0;
+
+    for session in sessions {
+        let (Some(start), Some(end)) = (session.start_time(), session.end_time()) else { continue };
+        let secs = range.overlap_secs(start, end);
+        if secs == 0 {
+            continue;
+        }
+
+        stats.total_secs += secs;
+        stats.session_count += 1;
+        lengths_total += session.duration_secs();
+        stats.longest_secs = stats.longest_secs.max(session.duration_secs());
+        *per_game.entry(session.game_id).or_insert(0) += secs;
+    }
+
+    if stats.session_count > 0 {
+        stats.average_secs = lengths_total / stats.session_count as u64;
+    }
+    stats.active_days = playtime_per_day(sessions, range).len();
+
+    stats.per_game = per_game.into_iter().collect();
+    stats.per_game.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::duration::format_duration;
+    use crate::core::json_storage::SessionId;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn time(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    /// Session of `game` ending at `end` ("%Y-%m-%d %H:%M:%S") after `secs` seconds.
+    fn session(game: GameId, end: &str, secs: u64) -> Session {
+        Session {
+            id: SessionId::random(),
+            game_id: game,
+            date: end.to_string(),
+            duration: format_duration(secs),
+            note: String::new(),
+            progress: String::new(),
+        }
+    }
+
+    #[test]
+    fn session_crossing_midnight_is_split_across_days() {
+        let game = GameId::random();
+        // 23:00 to 01:30
+        let sessions = [session(game, "2025-03-11 01:30:00", 9000)];
+
+        let days = playtime_per_day(&sessions, &DateRange::all_time());
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[&date("2025-03-10")], 3600);
+        assert_eq!(days[&date("2025-03-11")], 5400);
+    }
+
+    #[test]
+    fn range_keeps_only_the_part_inside() {
+        let game = GameId::random();
+        let sessions = [session(game, "2025-03-11 01:30:00", 9000)];
+        let range = DateRange::days(date("2025-03-11"), date("2025-03-11"));
+
+        let stats = range_statistics(&sessions, &range);
+
+        assert_eq!(stats.total_secs, 5400);
+        assert_eq!(stats.session_count, 1);
+        assert_eq!(stats.average_secs, 9000);
+        assert_eq!(stats.active_days, 1);
+        assert_eq!(stats.per_game, vec![(game, 5400)]);
+    }
+
+    #[test]
+    fn week_range_runs_monday_to_sunday() {
+        // Sunday belongs to the week that started the Monday before
+        let range = Period::ThisWeek.range(date("2025-03-16"));
+        assert_eq!(range, DateRange::days(date("2025-03-10"), date("2025-03-16")));
+
+        let range = Period::ThisWeek.range(date("2025-03-10"));
+        assert_eq!(range.start, time("2025-03-10 00:00:00"));
+        assert_eq!(range.end, time("2025-03-17 00:00:00"));
+
+        // Weeks crossing the year
+        let range = Period::ThisWeek.range(date("2025-01-01"));
+        assert_eq!(range, DateRange::days(date("2024-12-30"), date("2025-01-05")));
+    }
+
+    #[test]
+    fn month_range_covers_the_whole_month() {
+        let range = Period::ThisMonth.range(date("2024-02-29"));
+        assert_eq!(range.start, time("2024-02-01 00:00:00"));
+        assert_eq!(range.end, time("2024-03-01 00:00:00"));
+
+        let range = Period::ThisMonth.range(date("2025-12-01"));
+        assert_eq!(range.start, time("2025-12-01 00:00:00"));
+        assert_eq!(range.end, time("2026-01-01 00:00:00"));
+    }
+
+    #[test]
+    fn overlap_is_clipped_to_the_range() {
+        let range = DateRange::days(date("2025-03-10"), date("2025-03-10"));
+
+        assert_eq!(range.overlap_secs(time("2025-03-09 23:00:00"), time("2025-03-10 01:00:00")), 3600);
+        assert_eq!(range.overlap_secs(time("2025-03-10 23:30:00"), time("2025-03-11 02:00:00")), 1800);
+        assert_eq!(range.overlap_secs(time("2025-03-09 12:00:00"), time("2025-03-12 12:00:00")), 86400);
+        assert_eq!(range.overlap_secs(time("2025-03-11 00:00:00"), time("2025-03-11 01:00:00")), 0);
+        assert_eq!(range.overlap_secs(time("2025-03-09 22:00:00"), time("2025-03-10 00:00:00")), 0);
+    }
+
+    #[test]
+    fn histogram_buckets_include_start_and_exclude_end() {
+        let game = GameId::random();
+        let sessions = [
+            session(game, "2025-03-10 10:00:00", 0),
+            session(game, "2025-03-10 11:00:00", 1799),
+            session(game, "2025-03-10 12:00:00", 1800),
+            session(game, "2025-03-10 13:00:00", 3599),
+            session(game, "2025-03-10 14:00:00", 7200),
+        ];
+
+        assert_eq!(session_length_histogram(&sessions, 1800), vec![2, 2, 0, 0, 1]);
+        assert!(session_length_histogram(&[], 1800).is_empty());
+    }
+
+    #[test]
+    fn per_week_sums_by_monday() {
+        let game = GameId::random();
+        let sessions = [
+            session(game, "2025-03-10 10:00:00", 600),
+            session(game, "2025-03-16 10:00:00", 600),
+            session(game, "2025-03-17 10:00:00", 600),
+        ];
+
+        let weeks = playtime_per_week(&sessions, &DateRange::all_time());
+
+        assert_eq!(weeks.into_iter().collect::<Vec<_>>(), vec![(date("2025-03-10"), 1200), (date("2025-03-17"), 600)]);
+    }
+
+    #[test]
+    fn time_distribution_splits_at_hours() {
+        let game = GameId::random();
+        // Monday 10:30 to 12:15
+        let sessions = [session(game, "2025-03-10 12:15:00", 6300)];
+
+        let distribution = time_distribution(&sessions, &DateRange::all_time());
+
+        assert_eq!(distribution.matrix[0][10], 1800);
+        assert_eq!(distribution.matrix[0][11], 3600);
+        assert_eq!(distribution.matrix[0][12], 900);
+        assert_eq!(distribution.matrix[0].iter().sum::<u64>(), 6300);
+        assert_eq!(distribution.weekday_totals[0], 6300);
+        assert_eq!(distribution.weekday_averages[0], 6300);
+        assert_eq!(distribution.weekday_totals[1..].iter().sum::<u64>(), 0);
+    }
+
+    #[test]
+    fn time_distribution_crosses_into_the_next_weekday() {
+        let game = GameId::random();
+        // Sunday 23:30 to Monday 00:30
+        let sessions = [session(game, "2025-03-17 00:30:00", 3600)];
+
+        let distribution = time_distribution(&sessions, &DateRange::all_time());
+
+        assert_eq!(distribution.matrix[6][23], 1800);
+        assert_eq!(distribution.matrix[0][0], 1800);
+    }
+}
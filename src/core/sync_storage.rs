@@ -0,0 +1,780 @@
+use crate::core::duration::{format_date_time, format_duration};
+use crate::core::json_storage::{
+    Game, GameId, SESSIONS_FILE, Session, SessionId, load_all_sessions, load_deleted_games, load_games,
+    save_all_sessions, save_deleted_games, save_games,
+};
+use crate::core::library_merge::{GameField, merged_history};
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// Folder with one append-only session log per device. Its presence switches the data folder to
+/// the sync-safe layout: no file in it is ever written by two devices, so sync tools never have to
+/// choose between two versions.
+pub const LOGS_DIR: &str = "session-logs";
+const LOG_EXTENSION: &str = "jsonl";
+/// Part of the name Syncthing gives the losing copy of a file changed on two devices.
+const CONFLICT_MARKER: &str = ".sync-conflict";
+
+static DEVICE_ID: OnceLock<String> = OnceLock::new();
+
+/// A new random name for this device's log.
+pub fn new_device_id() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// Sets the name of this device's log. Has to happen before the first session is written.
+pub fn set_device_id(id: &str) {
+    let _ = DEVICE_ID.set(id.to_string());
+}
+
+fn device_id() -> &'static str {
+    DEVICE_ID.get_or_init(new_device_id)
+}
+
+fn own_log() -> PathBuf {
+    Path::new(LOGS_DIR).join(format!("{}.{}", device_id(), LOG_EXTENSION))
+}
+
+/// Whether the data folder stores sessions in device logs instead of `sessions.json`.
+pub fn is_enabled() -> bool {
+    Path::new(LOGS_DIR).is_dir()
+}
+
+fn is_conflict_copy(name: &str) -> bool {
+    name.contains(CONFLICT_MARKER)
+}
+
+/// A change to one session.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum Change {
+    /// The session was added or edited, this is all of it.
+    Put {
+        session: Session,
+    },
+    Delete {
+        id: SessionId,
+    },
+}
+
+impl Change {
+    fn session_id(&self) -> SessionId {
+        match self {
+            Change::Put { session } => session.id,
+            Change::Delete { id } => *id,
+        }
+    }
+}
+
+/// One line of a device log.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+struct LogEntry {
+    /// When the change was made, in milliseconds since the Unix epoch.
+    at: i64,
+    #[serde(flatten)]
+    change: Change,
+}
+
+/// Orders changes of one session: later `at` wins, then the device sorting last, then the later line.
+/// Every device folds the logs in this order, so all of them end up with the same sessions.
+type ChangeOrder = (i64, String, usize);
+
+/// Entries of a log. Lines that don't parse, like one still being synced, are skipped.
+fn read_log(path: &Path) -> Vec<LogEntry> {
+    fs::read_to_string(path)
+        .map(|text| text.lines().filter_map(|line| serde_json::from_str(line).ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Logs in `dir` by device, conflict copies left out.
+fn device_logs(dir: &Path) -> Vec<(String, Vec<LogEntry>)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut logs: Vec<(String, Vec<LogEntry>)> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == LOG_EXTENSION))
+        .filter_map(|path| {
+            let device = path.file_stem()?.to_str()?.to_string();
+            (!is_conflict_copy(&device)).then(|| (device, read_log(&path)))
+        })
+        .collect();
+    logs.sort_by(|a, b| a.0.cmp(&b.0));
+    logs
+}
+
+/// The winning change of every session ever logged in `dir`, deletions included.
+fn latest_changes(dir: &Path) -> HashMap<SessionId, (ChangeOrder, Change)> {
+    let mut latest: HashMap<SessionId, (ChangeOrder, Change)> = HashMap::new();
+    for (device, entries) in device_logs(dir) {
+        for (line, entry) in entries.into_iter().enumerate() {
+            let order = (entry.at, device.clone(), line);
+            match latest.get(&entry.change.session_id()) {
+                Some((current, _)) if *current >= order => {}
+                _ => {
+                    latest.insert(entry.change.session_id(), (order, entry.change));
+                }
+            }
+        }
+    }
+    latest
+}
+
+/// Sessions of all device logs in `dir`, ordered by end time.
+pub fn merged_sessions(dir: &Path) -> Vec<Session> {
+    let mut sessions: Vec<Session> = latest_changes(dir)
+        .into_values()
+        .filter_map(|(_, change)| match change {
+            Change::Put { session } => Some(session),
+            Change::Delete { .. } => None,
+        })
+        .collect();
+    sessions.sort_by(|a, b| a.date.cmp(&b.date).then(a.id.cmp(&b.id)));
+    sessions
+}
+
+/// Whether the file is empty or its last line is complete.
+fn ends_with_newline(file: &mut File) -> bool {
+    let mut last = [0u8];
+    file.seek(SeekFrom::End(-1)).and_then(|_| file.read_exact(&mut last)).map_or(true, |_| last[0] == b'\n')
+}
+
+/// Appends entries to this device's log.
+fn append(entries: &[LogEntry]) -> Result<(), String> {
+    append_to(&own_log(), entries)
+}
+
+fn append_to(path: &Path, entries: &[LogEntry]) -> Result<(), String> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut text = String::new();
+    for entry in entries {
+        text.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+        text.push('\n');
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Can't open {}: {}", path.display(), e))?;
+    // A line cut off by a crash must not swallow the next one
+    if !ends_with_newline(&mut file) {
+        text.insert(0, '\n');
+    }
+    file.write_all(text.as_bytes())
+        .and_then(|_| file.flush())
+        .map_err(|e| format!("Can't write {}: {}", path.display(), e))
+}
+
+/// Time stamp of a new change, later than `previous` even if this device's clock is behind.
+fn change_time(previous: Option<&ChangeOrder>) -> i64 {
+    let now = Utc::now().timestamp_millis();
+    previous.map_or(now, |(at, _, _)| now.max(at + 1))
+}
+
+/// Appends to this device's log what turns the merged sessions into `sessions`.
+pub fn record_sessions(sessions: &[Session]) -> Result<(), String> {
+    record_sessions_in(Path::new(LOGS_DIR), &own_log(), sessions)
+}
+
+/// Appends to `log` what turns the merged sessions of `dir` into `sessions`.
+fn record_sessions_in(dir: &Path, log: &Path, sessions: &[Session]) -> Result<(), String> {
+    let latest = latest_changes(dir);
+    let mut kept = HashSet::new();
+    let mut entries = Vec::new();
+
+    for session in sessions {
+        let mut session = session.clone();
+        if session.id.is_nil() {
+            session.id = SessionId::random();
+        }
+        kept.insert(session.id);
+
+        let previous = latest.get(&session.id);
+        if !matches!(previous, Some((_, Change::Put { session: stored })) if *stored == session) {
+            entries
+                .push(LogEntry { at: change_time(previous.map(|(order, _)| order)), change: Change::Put { session } });
+        }
+    }
+    for (id, (order, change)) in &latest {
+        if matches!(change, Change::Put { .. }) && !kept.contains(id) {
+            entries.push(LogEntry { at: change_time(Some(order)), change: Change::Delete { id: *id } });
+        }
+    }
+    entries.sort_by_key(|e| e.change.session_id());
+
+    append_to(log, &entries)
+}
+
+/// Appends a new session to this device's log.
+pub fn record_session(session: &Session) -> Result<(), String> {
+    append(&[LogEntry { at: change_time(None), change: Change::Put { session: session.clone() } }])
+}
+
+/// Switches the data folder to device logs. The sessions become the first entries of this device's
+/// log and `sessions.json` is kept as `sessions.json.bak`. Other devices follow once the folder syncs.
+/// Returns the number of moved sessions.
+pub fn enable_sync_storage() -> Result<usize, String> {
+    if is_enabled() {
+        return Err("The data folder already uses device logs".to_string());
+    }
+
+    let sessions = load_all_sessions();
+    fs::create_dir_all(LOGS_DIR).map_err(|e| format!("Can't create {}: {}", LOGS_DIR, e))?;
+    if let Err(err) = record_sessions(&sessions) {
+        let _ = fs::remove_dir_all(LOGS_DIR);
+        return Err(err);
+    }
+    if Path::new(SESSIONS_FILE).is_file() {
+        let _ = fs::rename(SESSIONS_FILE, format!("{}.bak", SESSIONS_FILE));
+    }
+    Ok(sessions.len())
+}
+
+/// Device logs in the data folder with their number of changes, this device's one marked.
+pub fn device_summary() -> Vec<(String, usize, bool)> {
+    device_logs(Path::new(LOGS_DIR))
+        .into_iter()
+        .map(|(device, entries)| {
+            let own = device == device_id();
+            (device, entries.len(), own)
+        })
+        .collect()
+}
+
+/// What `pick_up_sync_files` merged.
+#[derive(Default)]
+pub struct SyncReport {
+    /// Files merged and removed.
+    pub files: usize,
+    pub sessions: usize,
+    pub games: usize,
+    /// Games known on both sides whose copy added something.
+    pub games_updated: usize,
+    /// Conflict copies of other data and game details that differ on both sides, left for the user.
+    pub skipped: Vec<String>,
+}
+
+impl SyncReport {
+    pub fn describe(&self) -> String {
+        let mut text = if self.files == 0 {
+            "No conflict copies found".to_string()
+        } else {
+            format!(
+                "Merged {} files: {} sessions and {} games added, {} games updated",
+                self.files, self.sessions, self.games, self.games_updated
+            )
+        };
+        if !self.skipped.is_empty() {
+            text.push_str(&format!("\nNot merged, check by hand: {}", self.skipped.join(", ")));
+        }
+        text
+    }
+}
+
+fn read_list<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("{} is damaged: {}", path.display(), e))
+}
+
+/// Merges a conflict copy of a game into the local one, the same way on every device:
+/// tags and status histories are joined, the status follows the latest change, and details
+/// empty on one side are filled from the other. Hours and last played come from the sessions.
+///
+/// Returns whether anything changed and the details that differ on both sides; those keep
+/// the local value.
+fn merge_game_copy(local: &mut Game, copy: &Game) -> (bool, Vec<GameField>) {
+    let before = serde_json::to_value(&*local).ok();
+
+    for tag in &copy.tags {
+        if !local.tags.contains(tag) {
+            local.tags.push(tag.clone());
+        }
+    }
+    local.status_history = merged_history(&local.status_history, &copy.status_history);
+    if let Some(latest) = local.status_history.last() {
+        local.status = latest.status;
+    }
+    if local.category_id.is_none() {
+        local.category_id = copy.category_id;
+    }
+    if local.added.is_empty() || (!copy.added.is_empty() && copy.added < local.added) {
+        local.added = copy.added.clone();
+    }
+    for (value, other) in [
+        (&mut local.path, &copy.path),
+        (&mut local.notes, &copy.notes),
+        (&mut local.cover, &copy.cover),
+        (&mut local.launch.arguments, &copy.launch.arguments),
+        (&mut local.launch.working_dir, &copy.launch.working_dir),
+    ] {
+        if value.trim().is_empty() {
+            *value = other.clone();
+        }
+    }
+
+    let conflicts = GameField::ALL
+        .into_iter()
+        .filter(|field| {
+            let other = field.value(copy);
+            !other.trim().is_empty() && field.value(local) != other
+        })
+        .filter(|field| *field != GameField::Status || local.status_history.is_empty())
+        .collect();
+
+    (serde_json::to_value(&*local).ok() != before, conflicts)
+}
+
+/// Copies entries of a conflicting log copy in `dir` its device's log doesn't have into `log`,
+/// this device's one. They keep their time stamps, so the merge result is the same on every device.
+fn pick_up_log_copy(dir: &Path, log: &Path, path: &Path) -> Result<(), String> {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let device = name.split(CONFLICT_MARKER).next().unwrap_or_default();
+    let main = read_log(&dir.join(format!("{}.{}", device, LOG_EXTENSION)));
+    let own = read_log(log);
+
+    let missing: Vec<LogEntry> =
+        read_log(path).into_iter().filter(|entry| !main.contains(entry) && !own.contains(entry)).collect();
+    append_to(log, &missing)
+}
+
+/// Rewrites this device's log keeping only the winning change of each session among its own ones.
+/// The dropped ones lost anyway, and only this device writes the file, so rewriting it is safe.
+fn compact_log(path: &Path) -> Result<(), String> {
+    let entries = read_log(path);
+
+    let mut latest: HashMap<SessionId, (i64, usize)> = HashMap::new();
+    for (line, entry) in entries.iter().enumerate() {
+        let order = (entry.at, line);
+        let current = latest.entry(entry.change.session_id()).or_insert(order);
+        *current = (*current).max(order);
+    }
+    if latest.len() == entries.len() {
+        return Ok(());
+    }
+
+    let mut text = String::new();
+    for (line, entry) in entries.iter().enumerate() {
+        if latest.get(&entry.change.session_id()) == Some(&(entry.at, line)) {
+            text.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+            text.push('\n');
+        }
+    }
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, text).map_err(|e| format!("Can't write {}: {}", temp.display(), e))?;
+    fs::rename(&temp, path).map_err(|e| format!("Can't replace {}: {}", path.display(), e))
+}
+
+/// Recomputes total time and last played of games that have sessions, which may come from other devices.
+fn recalculate_totals() {
+    let sessions = load_all_sessions();
+    let mut games = load_games();
+    let mut changed = false;
+
+    for game in &mut games {
+        let own: Vec<&Session> = sessions.iter().filter(|s| s.game_id == game.id).collect();
+        if own.is_empty() {
+            continue;
+        }
+        let hours = format_duration(own.iter().map(|s| s.duration_secs()).sum());
+        let last_played = own.iter().filter_map(|s| s.end_time()).max().map(format_date_time).unwrap_or_default();
+        if game.hours != hours || game.last_played != last_played {
+            game.hours = hours;
+            game.last_played = last_played;
+            changed = true;
+        }
+    }
+
+    if changed {
+        save_games(&games);
+    }
+}
+
+/// Merges what a sync tool left in the data folder: conflict copies of `games.json`, `sessions.json`,
+/// `deleted-games.json` and device logs, and a `sessions.json` written by a device still on the old layout.
+/// Only games and sessions this library never had are taken, so sessions and games deleted here don't
+/// come back from an old copy. Merged files are removed, conflict copies of other data are reported.
+pub fn pick_up_sync_files() -> Result<SyncReport, String> {
+    let mut report = SyncReport::default();
+    let mut game_copies = Vec::new();
+    let mut session_copies = Vec::new();
+    let mut deleted_copies = Vec::new();
+
+    for path in fs::read_dir(".").into_iter().flatten().filter_map(|e| e.ok().map(|e| e.path())) {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_string();
+        if !is_conflict_copy(&name) {
+            continue;
+        }
+        if name.starts_with("games.") && name.ends_with(".json") {
+            game_copies.push(path);
+        } else if name.starts_with("deleted-games.") && name.ends_with(".json") {
+            deleted_copies.push(path);
+        } else if name.starts_with("sessions.") && name.ends_with(".json") {
+            session_copies.push(path);
+        } else {
+            report.skipped.push(name);
+        }
+    }
+    if is_enabled() && Path::new(SESSIONS_FILE).is_file() {
+        session_copies.push(PathBuf::from(SESSIONS_FILE));
+    }
+
+    if is_enabled() {
+        for path in fs::read_dir(LOGS_DIR).into_iter().flatten().filter_map(|e| e.ok().map(|e| e.path())) {
+            if path.file_name().and_then(|n| n.to_str()).is_some_and(is_conflict_copy) {
+                pick_up_log_copy(Path::new(LOGS_DIR), &own_log(), &path)?;
+                let _ = fs::remove_file(&path);
+                report.files += 1;
+            }
+        }
+    }
+
+    let mut deleted = load_deleted_games();
+    if !deleted_copies.is_empty() {
+        for path in &deleted_copies {
+            for id in read_list::<GameId>(path)? {
+                if !deleted.contains(&id) {
+                    deleted.push(id);
+                }
+            }
+        }
+        save_deleted_games(&deleted);
+    }
+
+    if !game_copies.is_empty() {
+        let mut games = load_games();
+        for path in &game_copies {
+            let copy: Vec<Game> = read_list(path)?;
+            for game in copy.into_iter().filter(|g| !g.id.is_legacy() && !deleted.contains(&g.id)) {
+                match games.iter_mut().find(|g| g.id == game.id) {
+                    Some(local) => {
+                        let (updated, conflicts) = merge_game_copy(local, &game);
+                        report.games_updated += updated as usize;
+                        report.skipped.extend(
+                            conflicts.iter().map(|field| format!("{} of {}", field.label().to_lowercase(), local.name)),
+                        );
+                    }
+                    None => {
+                        games.push(game);
+                        report.games += 1;
+                    }
+                }
+            }
+        }
+        save_games(&games);
+    }
+
+    if !session_copies.is_empty() {
+        let games = load_games();
+        let mut sessions = load_all_sessions();
+        let known: HashSet<SessionId> = if is_enabled() {
+            latest_changes(Path::new(LOGS_DIR)).into_keys().collect()
+        } else {
+            sessions.iter().map(|s| s.id).collect()
+        };
+
+        for path in &session_copies {
+            let copy: Vec<Session> = read_list(path)?;
+            for mut session in copy {
+                let duplicate = known.contains(&session.id)
+                    || sessions
+                        .iter()
+                        .any(|s| s.id == session.id || (s.game_id == session.game_id && s.date == session.date));
+                if duplicate || !games.iter().any(|g| g.id == session.game_id) {
+                    continue;
+                }
+                if session.id.is_nil() {
+                    session.id = SessionId::random();
+                }
+                sessions.push(session);
+                report.sessions += 1;
+            }
+        }
+        save_all_sessions(&sessions);
+    }
+
+    for path in game_copies.iter().chain(&session_copies).chain(&deleted_copies) {
+        if path == Path::new(SESSIONS_FILE) {
+            let _ = fs::rename(path, format!("{}.bak", SESSIONS_FILE));
+        } else {
+            let _ = fs::remove_file(path);
+        }
+        report.files += 1;
+    }
+
+    if is_enabled() {
+        compact_log(&own_log())?;
+    }
+    if is_enabled() || report.sessions > 0 {
+        recalculate_totals();
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::json_storage::{GameId, LaunchSettings, PlayStatus, StatusChange};
+    use crate::core::scratch_dir::ScratchDir;
+
+    fn game(id: GameId, name: &str) -> Game {
+        Game {
+            id,
+            name: name.to_string(),
+            path: String::new(),
+            hours: "0h 0m 0s".to_string(),
+            category_id: None,
+            sort_index: 0,
+            added: String::new(),
+            last_played: String::new(),
+            tags: Vec::new(),
+            status: PlayStatus::Planned,
+            status_history: Vec::new(),
+            launch: LaunchSettings::default(),
+            notes: String::new(),
+            cover: String::new(),
+        }
+    }
+
+    fn change(status: PlayStatus, date: &str) -> StatusChange {
+        StatusChange { status, date: date.to_string() }
+    }
+
+    fn log_path(dir: &ScratchDir, device: &str) -> PathBuf {
+        dir.0.join(format!("{}.{}", device, LOG_EXTENSION))
+    }
+
+    fn session(game_id: GameId, date: &str, note: &str) -> Session {
+        Session {
+            id: SessionId::random(),
+            game_id,
+            date: date.to_string(),
+            duration: "1h 0m 0s".to_string(),
+            note: note.to_string(),
+            progress: String::new(),
+        }
+    }
+
+    fn put(at: i64, session: &Session) -> LogEntry {
+        LogEntry { at, change: Change::Put { session: session.clone() } }
+    }
+
+    fn delete(at: i64, session: &Session) -> LogEntry {
+        LogEntry { at, change: Change::Delete { id: session.id } }
+    }
+
+    fn notes(sessions: &[Session]) -> Vec<&str> {
+        sessions.iter().map(|s| s.note.as_str()).collect()
+    }
+
+    #[test]
+    fn later_change_then_later_device_then_later_line_wins() {
+        let dir = ScratchDir::new();
+        let game = GameId::random();
+        let first = session(game, "2025-03-01 22:00:00", "");
+        let second = session(game, "2025-03-02 22:00:00", "");
+        let third = session(game, "2025-03-03 22:00:00", "");
+        let edit = |s: &Session, note: &str| Session { note: note.to_string(), ..s.clone() };
+
+        append_to(
+            &log_path(&dir, "a"),
+            &[
+                put(300, &edit(&first, "a, latest")),
+                put(100, &edit(&second, "a")),
+                put(100, &edit(&third, "a, first line")),
+                put(100, &edit(&third, "a, second line")),
+            ],
+        )
+        .unwrap();
+        append_to(&log_path(&dir, "b"), &[put(200, &edit(&first, "b")), put(100, &edit(&second, "b, same time"))])
+            .unwrap();
+
+        let latest = latest_changes(&dir.0);
+        let note = |s: &Session| match &latest[&s.id].1 {
+            Change::Put { session } => session.note.clone(),
+            Change::Delete { .. } => String::new(),
+        };
+        assert_eq!(note(&first), "a, latest");
+        assert_eq!(note(&second), "b, same time");
+        assert_eq!(note(&third), "a, second line");
+        assert_eq!(latest[&third.id].0, (100, "a".to_string(), 3));
+    }
+
+    #[test]
+    fn merged_sessions_leave_out_deleted_ones_and_follow_end_time() {
+        let dir = ScratchDir::new();
+        let game = GameId::random();
+        let late = session(game, "2025-03-05 22:00:00", "late");
+        let early = session(game, "2025-03-01 22:00:00", "early");
+        let deleted = session(game, "2025-03-03 22:00:00", "deleted");
+
+        append_to(&log_path(&dir, "a"), &[put(100, &late), put(100, &deleted)]).unwrap();
+        append_to(&log_path(&dir, "b"), &[put(100, &early), delete(200, &deleted)]).unwrap();
+        fs::write(dir.0.join(format!("b{}-20250306.{}", CONFLICT_MARKER, LOG_EXTENSION)), "").unwrap();
+
+        assert_eq!(notes(&merged_sessions(&dir.0)), ["early", "late"]);
+    }
+
+    #[test]
+    fn recording_logs_edits_and_deletions() {
+        let dir = ScratchDir::new();
+        let log = log_path(&dir, "a");
+        let game = GameId::random();
+        let kept = session(game, "2025-03-01 22:00:00", "kept");
+        let removed = session(game, "2025-03-02 22:00:00", "removed");
+
+        record_sessions_in(&dir.0, &log, &[kept.clone(), removed.clone()]).unwrap();
+        let edited = Session { note: "kept, edited".to_string(), ..kept.clone() };
+        record_sessions_in(&dir.0, &log, std::slice::from_ref(&edited)).unwrap();
+        // Nothing changed, nothing logged
+        record_sessions_in(&dir.0, &log, std::slice::from_ref(&edited)).unwrap();
+
+        let entries = read_log(&log);
+        assert_eq!(entries.len(), 4);
+        let at = |change: Change| entries.iter().find(|e| e.change == change).map(|e| e.at);
+        let put_at = at(Change::Put { session: removed.clone() }).unwrap();
+        assert!(at(Change::Delete { id: removed.id }).is_some_and(|deleted_at| deleted_at > put_at));
+        assert_eq!(notes(&merged_sessions(&dir.0)), ["kept, edited"]);
+    }
+
+    #[test]
+    fn conflict_copies_of_logs_are_picked_up_once() {
+        let dir = ScratchDir::new();
+        let game = GameId::random();
+        let synced = session(game, "2025-03-01 22:00:00", "synced");
+        let lost = session(game, "2025-03-02 22:00:00", "only in the copy");
+        let copy = dir.0.join(format!("b{}-20250306.{}", CONFLICT_MARKER, LOG_EXTENSION));
+
+        append_to(&log_path(&dir, "b"), &[put(100, &synced)]).unwrap();
+        append_to(&copy, &[put(100, &synced), put(200, &lost)]).unwrap();
+        pick_up_log_copy(&dir.0, &log_path(&dir, "a"), &copy).unwrap();
+        pick_up_log_copy(&dir.0, &log_path(&dir, "a"), &copy).unwrap();
+
+        assert!(read_log(&log_path(&dir, "a")) == vec![put(200, &lost)]);
+        assert_eq!(notes(&merged_sessions(&dir.0)), ["synced", "only in the copy"]);
+    }
+
+    #[test]
+    fn compaction_keeps_the_winning_change_of_each_session() {
+        let dir = ScratchDir::new();
+        let log = log_path(&dir, "a");
+        let game = GameId::random();
+        let edited = session(game, "2025-03-01 22:00:00", "first");
+        let deleted = session(game, "2025-03-02 22:00:00", "deleted");
+        let newer = Session { note: "second".to_string(), ..edited.clone() };
+
+        append_to(&log, &[put(100, &edited), put(100, &deleted), put(300, &newer), delete(200, &deleted)]).unwrap();
+        append_to(&log_path(&dir, "b"), &[put(250, &Session { note: "from b".to_string(), ..edited.clone() })])
+            .unwrap();
+        let before = merged_sessions(&dir.0);
+        compact_log(&log).unwrap();
+
+        assert!(read_log(&log) == vec![put(300, &newer), delete(200, &deleted)]);
+        assert!(merged_sessions(&dir.0) == before);
+        assert_eq!(notes(&before), ["second"]);
+    }
+
+    #[test]
+    fn devices_end_up_with_the_same_sessions_whatever_syncs_first() {
+        let game = GameId::random();
+        let shared = session(game, "2025-03-01 22:00:00", "shared");
+        let raced = session(game, "2025-03-02 22:00:00", "raced");
+        let edit = |note: &str| Session { note: note.to_string(), ..raced.clone() };
+        // Same moment on both devices: "b" sorts last, so its edit beats the deletion on "a"
+        let a = vec![put(100, &shared), put(100, &raced), delete(300, &raced), put(400, &edit("a"))];
+        let b =
+            vec![delete(250, &shared), put(300, &edit("b")), put(500, &session(game, "2025-03-03 22:00:00", "new"))];
+
+        let mut results = Vec::new();
+        for (first, second) in [(("a", &a), ("b", &b)), (("b", &b), ("a", &a))] {
+            let dir = ScratchDir::new();
+            for (device, entries) in [first, second] {
+                for entry in entries {
+                    append_to(&log_path(&dir, device), std::slice::from_ref(entry)).unwrap();
+                }
+            }
+            results.push(merged_sessions(&dir.0));
+        }
+
+        assert!(results[0] == results[1]);
+        assert_eq!(notes(&results[0]), ["a", "new"]);
+    }
+
+    #[test]
+    fn deletion_racing_an_edit_goes_to_the_later_change() {
+        let game = GameId::random();
+        let raced = session(game, "2025-03-02 22:00:00", "raced");
+        let edited = Session { note: "edited".to_string(), ..raced.clone() };
+
+        for (deleted_at, edited_at, expected) in
+            [(300, 200, Vec::<&str>::new()), (200, 300, vec!["edited"]), (300, 300, vec!["edited"])]
+        {
+            let logs =
+                [("a", vec![put(100, &raced), delete(deleted_at, &raced)]), ("b", vec![put(edited_at, &edited)])];
+            let mut results = Vec::new();
+            for order in [[0, 1], [1, 0]] {
+                let dir = ScratchDir::new();
+                for (device, entries) in order.map(|i| &logs[i]) {
+                    append_to(&log_path(&dir, device), entries).unwrap();
+                }
+                results.push(merged_sessions(&dir.0));
+            }
+            assert!(results[0] == results[1]);
+            assert_eq!(notes(&results[0]), expected);
+        }
+    }
+
+    #[test]
+    fn game_copies_merge_the_same_way_on_both_devices() {
+        let id = GameId::random();
+        let mut a = game(id, "Saya no Uta");
+        a.tags = vec!["nitroplus".to_string()];
+        a.status = PlayStatus::Playing;
+        a.status_history = vec![change(PlayStatus::Playing, "2025-03-01 10:00:00")];
+        a.path = "/games/saya/saya.exe".to_string();
+        let mut b = game(id, "Saya no Uta");
+        b.tags = vec!["horror".to_string()];
+        b.status = PlayStatus::Completed;
+        b.status_history = vec![change(PlayStatus::Completed, "2025-03-05 22:00:00")];
+        b.notes = "True end done".to_string();
+
+        let (mut on_a, mut on_b) = (a.clone(), b.clone());
+        assert_eq!(merge_game_copy(&mut on_a, &b), (true, Vec::new()));
+        assert_eq!(merge_game_copy(&mut on_b, &a), (true, Vec::new()));
+
+        for merged in [&on_a, &on_b] {
+            assert!(merged.status == PlayStatus::Completed);
+            assert_eq!(merged.status_history.len(), 2);
+            assert_eq!(merged.tags.len(), 2);
+            assert_eq!(merged.path, "/games/saya/saya.exe");
+            assert_eq!(merged.notes, "True end done");
+        }
+        assert_eq!(merge_game_copy(&mut on_a, &b), (false, Vec::new()));
+    }
+
+    #[test]
+    fn details_set_differently_on_both_devices_are_reported() {
+        let id = GameId::random();
+        let mut local = game(id, "Saya no Uta");
+        local.notes = "Local note".to_string();
+        let mut copy = game(id, "The Song of Saya");
+        copy.notes = "Other note".to_string();
+
+        let (_, conflicts) = merge_game_copy(&mut local, &copy);
+
+        assert!(conflicts == vec![GameField::Name, GameField::Notes]);
+        assert_eq!(local.name, "Saya no Uta");
+        assert_eq!(local.notes, "Local note");
+    }
+}
@@ -0,0 +1,78 @@
+use crate::core::json_storage::{Game, GameId, load_games, save_games};
+
+/// Trims a tag typed by the user. Returns `None` for blank input.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim();
+    if tag.is_empty() { None } else { Some(tag.to_string()) }
+}
+
+/// Returns all tags used in the library, sorted and without duplicates, with the number of games using each.
+pub fn all_tags(games: &[Game]) -> Vec<(String, usize)> {
+    let mut tags: Vec<(String, usize)> = Vec::new();
+
+    for tag in games.iter().flat_map(|g| &g.tags) {
+        match tags.iter_mut().find(|(t, _)| t == tag) {
+            Some((_, count)) => *count += 1,
+            None => tags.push((tag.clone(), 1)),
+        }
+    }
+
+    tags.sort_by_key(|(tag, _)| tag.to_lowercase());
+    tags
+}
+
+/// Replaces the tags of one game.
+pub fn set_game_tags(game_id: GameId, tags: Vec<String>) {
+    let mut games = load_games();
+
+    if let Some(game) = games.iter_mut().find(|g| g.id == game_id) {
+        game.tags.clear();
+        for tag in tags.iter().filter_map(|t| normalize_tag(t)) {
+            if !game.tags.contains(&tag) {
+                game.tags.push(tag);
+            }
+        }
+    }
+
+    save_games(&games);
+}
+
+/// Replaces every tag from `sources` with `target` in all games.
+///
+/// Renaming is merging a single tag; merging into a tag the game already has
+/// leaves just one copy of it.
+pub fn merge_tags(sources: &[String], target: &str) {
+    let Some(target) = normalize_tag(target) else {
+        return;
+    };
+
+    let mut games = load_games();
+    for game in games.iter_mut().filter(|g| g.tags.iter().any(|t| sources.contains(t))) {
+        let mut tags = Vec::new();
+        for tag in &game.tags {
+            let tag = if sources.contains(tag) { target.clone() } else { tag.clone() };
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        game.tags = tags;
+    }
+
+    save_games(&games);
+}
+
+/// Renames a tag in all games.
+pub fn rename_tag(old: &str, new: &str) {
+    merge_tags(&[old.to_string()], new);
+}
+
+/// Removes a tag from all games.
+pub fn delete_tag(tag: &str) {
+    let mut games = load_games();
+
+    for game in games.iter_mut() {
+        game.tags.retain(|t| t != tag);
+    }
+
+    save_games(&games);
+}
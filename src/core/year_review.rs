@@ -0,0 +1,261 @@
+use crate::core::duration::{format_duration, parse_date_time};
+use crate::core::json_storage::{Game, GameId, PlayStatus, Session};
+use crate::core::statistics::{DateRange, playtime_per_day, range_statistics};
+use chrono::{Datelike, NaiveDate};
+use std::fmt::Write;
+
+/// How many games the top list holds.
+const TOP_GAMES: usize = 5;
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// The longest session of a year.
+#[derive(Clone)]
+pub struct LongestSession {
+    pub game_name: String,
+    /// End of the session.
+    pub date: String,
+    pub secs: u64,
+}
+
+/// "Wrapped" summary of one year of playing.
+#[derive(Clone, Default)]
+pub struct YearReview {
+    pub year: i32,
+    pub total_secs: u64,
+    pub session_count: usize,
+    pub active_days: usize,
+    /// Most played games with their playtime in the year.
+    pub top_games: Vec<(String, u64)>,
+    pub longest_session: Option<LongestSession>,
+    pub busiest_day: Option<(NaiveDate, u64)>,
+    /// Playtime per month, January first.
+    pub monthly_secs: [u64; 12],
+    /// Games first played in the year.
+    pub games_started: usize,
+    /// Games marked completed in the year.
+    pub games_finished: usize,
+    pub previous_total_secs: u64,
+    pub previous_session_count: usize,
+    pub previous_active_days: usize,
+}
+
+impl YearReview {
+    /// Index of the month with the most playtime.
+    pub fn busiest_month(&self) -> Option<usize> {
+        (0..12).filter(|&m| self.monthly_secs[m] > 0).max_by_key(|&m| self.monthly_secs[m])
+    }
+}
+
+/// Name of a game by id.
+fn game_name(games: &[Game], game_id: GameId) -> String {
+    games.iter().find(|g| g.id == game_id).map_or("Unknown game".to_string(), |g| g.name.clone())
+}
+
+/// Builds the summary of `year`, compared with the year before.
+pub fn year_review(games: &[Game], sessions: &[Session], year: i32) -> YearReview {
+    let first = NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or_default();
+    let last = NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or_default();
+    let range = DateRange::days(first, last);
+    let previous = DateRange::days(
+        NaiveDate::from_ymd_opt(year - 1, 1, 1).unwrap_or_default(),
+        NaiveDate::from_ymd_opt(year - 1, 12, 31).unwrap_or_default(),
+    );
+
+    let stats = range_statistics(sessions, &range);
+    let previous_stats = range_statistics(sessions, &previous);
+    let days = playtime_per_day(sessions, &range);
+
+    let mut monthly_secs = [0; 12];
+    for (day, secs) in &days {
+        monthly_secs[day.month0() as usize] += secs;
+    }
+
+    let longest_session = sessions
+        .iter()
+        .filter(|s| s.end_time().is_some_and(|end| end.year() == year))
+        .max_by_key(|s| s.duration_secs())
+        .map(|s| LongestSession {
+            game_name: game_name(games, s.game_id),
+            date: s.date.clone(),
+            secs: s.duration_secs(),
+        });
+
+    // A game counts as started in the year its earliest session started
+    let games_started = games
+        .iter()
+        .filter(|g| {
+            let first_start = sessions.iter().filter(|s| s.game_id == g.id).filter_map(|s| s.start_time()).min();
+            first_start.is_some_and(|start| start.year() == year)
+        })
+        .count();
+    let games_finished = games
+        .iter()
+        .filter(|g| {
+            g.status_history.iter().any(|change| {
+                change.status == PlayStatus::Completed
+                    && parse_date_time(&change.date).is_some_and(|date| date.year() == year)
+            })
+        })
+        .count();
+
+    YearReview {
+        year,
+        total_secs: stats.total_secs,
+        session_count: stats.session_count,
+        active_days: stats.active_days,
+        top_games: stats.per_game.iter().take(TOP_GAMES).map(|(id, secs)| (game_name(games, *id), *secs)).collect(),
+        longest_session,
+        busiest_day: days.iter().max_by_key(|(_, secs)| **secs).map(|(day, secs)| (*day, *secs)),
+        monthly_secs,
+        games_started,
+        games_finished,
+        previous_total_secs: previous_stats.total_secs,
+        previous_session_count: previous_stats.session_count,
+        previous_active_days: previous_stats.active_days,
+    }
+}
+
+/// Change against the previous year, e.g. `"+25%"`, or `None` if there is nothing to compare to.
+pub fn change_percent(current: u64, previous: u64) -> Option<String> {
+    if previous == 0 {
+        return None;
+    }
+    let change = (current as f64 - previous as f64) / previous as f64 * 100.0;
+    Some(format!("{:+.0}%", change))
+}
+
+/// Escapes text for HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Inline SVG column chart of the monthly playtime.
+fn monthly_svg(monthly_secs: &[u64; 12]) -> String {
+    let max = monthly_secs.iter().copied().max().unwrap_or(0).max(1);
+    let mut svg = String::from(r#"<svg viewBox="0 0 480 180" width="480" height="180">"#);
+
+    for (i, secs) in monthly_secs.iter().enumerate() {
+        let height = 140.0 * *secs as f64 / max as f64;
+        let x = 10.0 + i as f64 * 39.0;
+        let _ = write!(
+            svg,
+            r##"<rect x="{x:.0}" y="{:.1}" width="30" height="{height:.1}" rx="3" fill="#4caf50"><title>{}: {}</title></rect>"##,
+            150.0 - height,
+            MONTHS[i],
+            format_duration(*secs)
+        );
+        let _ = write!(
+            svg,
+            r#"<text x="{:.0}" y="170" font-size="11" text-anchor="middle">{}</text>"#,
+            x + 15.0,
+            MONTHS[i]
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Inline SVG bar chart of the top games.
+fn top_games_svg(top_games: &[(String, u64)]) -> String {
+    let max = top_games.iter().map(|(_, secs)| *secs).max().unwrap_or(0).max(1);
+    let height = top_games.len() * 30 + 10;
+    let mut svg = format!(r#"<svg viewBox="0 0 480 {height}" width="480" height="{height}">"#);
+
+    for (i, (name, secs)) in top_games.iter().enumerate() {
+        let y = 10 + i * 30;
+        let width = 260.0 * *secs as f64 / max as f64;
+        let _ = write!(
+            svg,
+            r##"<text x="0" y="{}" font-size="13">{}</text><rect x="150" y="{y}" width="{width:.1}" height="20" rx="3" fill="#2196f3"/><text x="{:.0}" y="{}" font-size="12">{}</text>"##,
+            y + 15,
+            escape_html(name),
+            156.0 + width,
+            y + 15,
+            format_duration(*secs)
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Renders the review as a standalone HTML page with embedded SVG charts.
+pub fn review_to_html(review: &YearReview) -> String {
+    let compare = |current: u64, previous: u64| {
+        change_percent(current, previous)
+            .map(|c| format!(" <small>({} vs {})</small>", c, review.year - 1))
+            .unwrap_or_default()
+    };
+    let mut html = String::new();
+
+    let _ = write!(
+        html,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{year} in review</title>
+<style>
+body {{ font-family: sans-serif; max-width: 560px; margin: 2em auto; color: #222; }}
+h1 {{ color: #4caf50; }}
+dl {{ display: grid; grid-template-columns: auto 1fr; gap: 0.3em 1em; }}
+dt {{ font-weight: bold; }}
+small {{ color: #777; }}
+</style>
+</head>
+<body>
+<h1>{year} in review</h1>
+<dl>
+<dt>Total time</dt><dd>{total}{total_change}</dd>
+<dt>Sessions</dt><dd>{sessions}{sessions_change}</dd>
+<dt>Active days</dt><dd>{days}{days_change}</dd>
+<dt>Games started</dt><dd>{started}</dd>
+<dt>Games finished</dt><dd>{finished}</dd>
+"#,
+        year = review.year,
+        total = format_duration(review.total_secs),
+        total_change = compare(review.total_secs, review.previous_total_secs),
+        sessions = review.session_count,
+        sessions_change = compare(review.session_count as u64, review.previous_session_count as u64),
+        days = review.active_days,
+        days_change = compare(review.active_days as u64, review.previous_active_days as u64),
+        started = review.games_started,
+        finished = review.games_finished,
+    );
+
+    if let Some(longest) = &review.longest_session {
+        let _ = writeln!(
+            html,
+            "<dt>Longest session</dt><dd>{} — {} ({})</dd>",
+            format_duration(longest.secs),
+            escape_html(&longest.game_name),
+            longest.date
+        );
+    }
+    if let Some((day, secs)) = review.busiest_day {
+        let _ = writeln!(html, "<dt>Busiest day</dt><dd>{} ({})</dd>", day, format_duration(secs));
+    }
+    if let Some(month) = review.busiest_month() {
+        let _ = writeln!(
+            html,
+            "<dt>Busiest month</dt><dd>{} ({})</dd>",
+            MONTHS[month],
+            format_duration(review.monthly_secs[month])
+        );
+    }
+    html.push_str("</dl>\n");
+
+    let _ = writeln!(html, "<h2>Playtime per month</h2>\n{}", monthly_svg(&review.monthly_secs));
+    if !review.top_games.is_empty() {
+        let _ = writeln!(html, "<h2>Top games</h2>\n{}", top_games_svg(&review.top_games));
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Short month name, `0` for January.
+pub fn month_name(month0: usize) -> &'static str {
+    MONTHS[month0 % 12]
+}
@@ -6,11 +6,29 @@
 //! - [`game_runner`]: manages the game loop or execution logic.
 //! - [`storage`]: handles persistence (saving and loading data).
 //! - [`ui`]: defines and runs the graphical user interface.
+//!
+//! Started with a command (see [`cli`]), it runs that instead of the GUI.
 
+mod cli;
 mod core;
 mod ui;
 
 /// Starts the application initializes and runs the `eframe` application loop.
 fn main() -> eframe::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        cli::attach_console();
+    }
+    if let Some(result) = cli::run(&args) {
+        match result {
+            Ok(message) => println!("{}", message),
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
     ui::app::run_gui()
 }
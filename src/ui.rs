@@ -1,6 +1,25 @@
 pub mod add_game_window;
 pub mod app;
+pub mod backup_window;
+pub mod categories_window;
+pub mod covers;
 pub mod delete_game_window;
+pub mod export_window;
+pub mod game_details_window;
+pub mod goals_window;
+pub mod heatmap_window;
+pub mod import_window;
+pub mod library_merge_window;
+pub mod limits_window;
 pub mod main_window;
+pub mod merge_games_window;
+pub mod overlaps_window;
+pub mod relink_window;
+pub mod session_charts;
+pub mod session_note_window;
 pub mod show_sessions_window;
+pub mod statistics_window;
+pub mod sync_window;
+pub mod tags_window;
 mod ui_patterns;
+pub mod year_review_window;
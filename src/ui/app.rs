@@ -1,51 +1,407 @@
-use crate::core::{icons, json_storage};
+use crate::core::backup::{BackupContents, RestoreMode};
+use crate::core::csv_export::CsvOptions;
+use crate::core::csv_import::ImportOptions;
+use crate::core::game_filter::SortColumn;
+use crate::core::goals::Streak;
+use crate::core::json_storage::{CategoryId, GameId, IdChanges, SessionId, StatusChange};
+use crate::core::library_merge::{ForeignLibrary, GameMerge};
+use crate::core::statistics::Period;
+use crate::core::{icons, json_storage, relink, session_crud, sync_storage};
 use crate::ui;
+use chrono::NaiveDate;
 use eframe::{App, CreationContext, Frame, NativeOptions, Storage, egui};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::mpsc::{Receiver, Sender, channel};
 
 /// Main app
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct PersistedState {
     pub games: Vec<json_storage::Game>,
+    pub categories: Vec<json_storage::Category>,
     pub show_add_game_window: bool,
     pub new_game_name: String,
-    pub editing_name: Option<u32>,
-    pub show_sessions_window: Option<u32>,
-    pub show_confirm_delete_window: Option<u32>,
+    pub editing_name: Option<GameId>,
+    pub show_sessions_window: Option<GameId>,
+    pub show_confirm_delete_window: Option<GameId>,
     pub dark_mode: bool,
+    pub session_note_draft: Option<SessionNoteDraft>,
+    pub session_search: String,
+    pub session_split_draft: Option<SessionSplitDraft>,
+    pub session_edit_error: Option<String>,
+    pub show_overlaps_window: bool,
+    pub kept_overlaps: Vec<String>,
+    pub merge_games_draft: Option<MergeGamesDraft>,
+    pub relink_draft: Option<RelinkDraft>,
+    pub show_categories_window: bool,
+    pub new_category_name: String,
+    pub collapsed_categories: Vec<Option<CategoryId>>,
+    pub selected_game: Option<GameId>,
+    pub games_search: String,
+    pub sort_column: Option<SortColumn>,
+    pub sort_descending: bool,
+    pub tag_filter: Vec<String>,
+    pub tag_filter_match_all: bool,
+    pub show_game_details: Option<GameId>,
+    pub new_tag_name: String,
+    pub show_tags_window: bool,
+    pub tag_edit_selection: Vec<String>,
+    pub tag_edit_target: String,
+    pub status_filter: Option<json_storage::PlayStatus>,
+    pub game_details_draft: Option<GameDetailsDraft>,
+    /// Show the library as a grid of covers instead of the table.
+    pub cover_view: bool,
+    pub show_statistics_window: bool,
+    pub statistics_period: Period,
+    pub show_heatmap_window: bool,
+    /// Year shown in the calendar, `None` for the current one.
+    pub heatmap_year: Option<i32>,
+    /// Day clicked in the calendar, `"YYYY-MM-DD"`.
+    pub heatmap_selected_day: Option<String>,
+    pub session_charts: SessionChartsState,
+    pub goals: Vec<json_storage::PlayGoal>,
+    pub show_goals_window: bool,
+    pub limits: Vec<json_storage::PlayLimit>,
+    pub show_limits_window: bool,
+    pub show_year_review_window: bool,
+    /// Year shown in the review, `None` for the current one.
+    pub year_review_year: Option<i32>,
+    /// Result of the last HTML export.
+    pub year_review_message: Option<String>,
+    pub show_export_window: bool,
+    pub csv_export: CsvExportState,
+    pub csv_import_draft: Option<CsvImportDraft>,
+    pub show_backup_window: bool,
+    /// Result of the last backup or restore.
+    pub backup_message: Option<String>,
+    /// Name of this device's session log when the data folder is synced.
+    pub device_id: String,
+    pub show_sync_window: bool,
+    /// Result of the last conflict pickup or layout switch.
+    pub sync_message: Option<String>,
 }
 
-pub struct GameUpdate {
-    pub game_id: u32,
-    pub hours: String,
+/// Note and progress typed in after a session has finished.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SessionNoteDraft {
+    pub game_id: GameId,
+    #[serde(default)]
+    pub session_id: SessionId,
+    /// End of the session, shown in the prompt.
+    pub date: String,
+    pub note: String,
+    pub progress: String,
+}
+
+/// Split point being entered for a session in the sessions window.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SessionSplitDraft {
+    #[serde(default)]
+    pub session_id: SessionId,
+    pub at: String,
+}
+
+/// Selection in the "Merge games" window.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct MergeGamesDraft {
+    pub donor_id: Option<GameId>,
+    pub target_id: Option<GameId>,
+    pub keep_donor_name: bool,
+    pub keep_donor_path: bool,
+    pub error: Option<String>,
+}
+
+/// Folder prefixes entered in the "Relink library folder" window.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct RelinkDraft {
+    pub old_prefix: String,
+    pub new_prefix: String,
+}
+
+/// Editable copy of the fields in the game details window.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GameDetailsDraft {
+    pub game_id: GameId,
+    pub name: String,
+    pub path: String,
+    pub arguments: String,
+    pub working_dir: String,
+    pub notes: String,
+    /// Why the last cover change failed.
+    #[serde(default)]
+    pub cover_error: Option<String>,
+}
+
+/// Date range and grouping of the charts in the sessions window.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct SessionChartsState {
+    /// First day, `"YYYY-MM-DD"`. Empty for the first session.
+    pub from: String,
+    /// Last day, `"YYYY-MM-DD"`. Empty for today.
+    pub to: String,
+    /// Group bars by week instead of day.
+    pub weekly: bool,
+}
+
+/// Settings of the CSV export window.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CsvExportState {
+    pub options: CsvOptions,
+    /// First day, `"YYYY-MM-DD"`. Empty for the first session.
+    pub from: String,
+    /// Last day, `"YYYY-MM-DD"`. Empty for today.
+    pub to: String,
+    /// Result of the last export.
+    pub message: Option<String>,
+}
+
+/// Preferences kept in backups. Window state, drafts and cached data stay out, they belong
+/// to one session on one machine.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Settings {
+    pub dark_mode: bool,
+    pub cover_view: bool,
+    pub sort_column: Option<SortColumn>,
+    pub sort_descending: bool,
+    pub tag_filter_match_all: bool,
+    pub statistics_period: Period,
+    pub csv_export: CsvOptions,
+}
+
+/// Progress of the CSV import wizard.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CsvImportDraft {
+    /// File being imported.
+    pub path: String,
+    /// Fields of every row in the file.
+    pub rows: Vec<Vec<String>>,
+    pub options: ImportOptions,
+    /// Showing the planned changes instead of the column mapping.
+    pub reviewing: bool,
+    pub error: Option<String>,
+    /// Result of the finished import.
+    pub finished: Option<String>,
+}
+
+/// Message from a running game's thread to the UI.
+pub enum GameUpdate {
+    /// The game's process started and moved it from "planned" to "playing".
+    Started { game_id: GameId, change: StatusChange },
+    /// The game exited and its session was saved.
+    Finished { game_id: GameId, hours: String, session_id: SessionId, session_date: String },
+    /// A session reminder fired.
+    Reminder { game_id: GameId, message: String },
 }
 
 pub struct TimeTrackerApp {
     pub state: PersistedState,
     pub updates_tx: Sender<GameUpdate>,
     pub updates_rx: Receiver<GameUpdate>,
+    /// Ids of games whose executable was not found.
+    pub missing_games: HashSet<GameId>,
+    /// Loaded cover textures by thumbnail file, `None` if the file couldn't be read.
+    pub cover_textures: HashMap<String, Option<egui::TextureHandle>>,
+    /// Reminders waiting to be dismissed, by game id.
+    pub reminders: Vec<(GameId, String)>,
+    /// Opened backup waiting for the user to confirm the restore.
+    pub pending_restore: Option<PendingRestore>,
+    /// Library from another machine under review.
+    pub library_merge: Option<LibraryMergeDraft>,
+    /// Progress of the goal shown next to "Add game", `None` until recomputed after sessions or goals changed.
+    pub goal_header: Option<GoalHeader>,
+}
+
+/// Cached progress of the headline goal, so the sessions aren't read every frame.
+pub struct GoalHeader {
+    /// Day the progress was computed for; it starts over when the period turns.
+    pub day: NaiveDate,
+    pub played: u64,
+    pub streak: Streak,
+}
+
+/// Another library and the reviewed decision for each of its games.
+pub struct LibraryMergeDraft {
+    pub library: ForeignLibrary,
+    pub plan: Vec<GameMerge>,
+    pub error: Option<String>,
+    /// Result of the finished merge.
+    pub finished: Option<String>,
+}
+
+/// A checked backup archive and how to restore it.
+pub struct PendingRestore {
+    pub path: String,
+    pub contents: BackupContents,
+    pub mode: RestoreMode,
 }
 
 impl Default for PersistedState {
     fn default() -> Self {
         Self {
             games: json_storage::load_games(),
+            categories: json_storage::load_categories(),
             show_add_game_window: false,
             new_game_name: String::new(),
             editing_name: None,
             show_sessions_window: None,
             show_confirm_delete_window: None,
             dark_mode: false,
+            session_note_draft: None,
+            session_search: String::new(),
+            session_split_draft: None,
+            session_edit_error: None,
+            show_overlaps_window: false,
+            kept_overlaps: Vec::new(),
+            merge_games_draft: None,
+            relink_draft: None,
+            show_categories_window: false,
+            new_category_name: String::new(),
+            collapsed_categories: Vec::new(),
+            selected_game: None,
+            games_search: String::new(),
+            sort_column: None,
+            sort_descending: false,
+            tag_filter: Vec::new(),
+            tag_filter_match_all: true,
+            show_game_details: None,
+            new_tag_name: String::new(),
+            show_tags_window: false,
+            tag_edit_selection: Vec::new(),
+            tag_edit_target: String::new(),
+            status_filter: None,
+            game_details_draft: None,
+            cover_view: false,
+            show_statistics_window: false,
+            statistics_period: Period::ThisWeek,
+            show_heatmap_window: false,
+            heatmap_year: None,
+            heatmap_selected_day: None,
+            session_charts: SessionChartsState::default(),
+            goals: json_storage::load_goals(),
+            show_goals_window: false,
+            limits: json_storage::load_limits(),
+            show_limits_window: false,
+            show_year_review_window: false,
+            year_review_year: None,
+            year_review_message: None,
+            show_export_window: false,
+            csv_export: CsvExportState::default(),
+            csv_import_draft: None,
+            show_backup_window: false,
+            backup_message: None,
+            device_id: sync_storage::new_device_id(),
+            show_sync_window: false,
+            sync_message: None,
+        }
+    }
+}
+
+impl PersistedState {
+    /// The preferences written into backups.
+    pub fn settings(&self) -> Settings {
+        Settings {
+            dark_mode: self.dark_mode,
+            cover_view: self.cover_view,
+            sort_column: self.sort_column,
+            sort_descending: self.sort_descending,
+            tag_filter_match_all: self.tag_filter_match_all,
+            statistics_period: self.statistics_period,
+            csv_export: self.csv_export.options.clone(),
+        }
+    }
+
+    /// Applies preferences read from a backup.
+    pub fn apply_settings(&mut self, settings: Settings) {
+        self.dark_mode = settings.dark_mode;
+        self.cover_view = settings.cover_view;
+        self.sort_column = settings.sort_column;
+        self.sort_descending = settings.sort_descending;
+        self.tag_filter_match_all = settings.tag_filter_match_all;
+        self.statistics_period = settings.statistics_period;
+        self.csv_export.options = settings.csv_export;
+    }
+
+    /// Points windows and drafts at the new ids of games and categories `migrate_legacy_ids`
+    /// gave random ones. Drafts of sessions stored without an id are dropped.
+    pub fn remap_ids(&mut self, changes: &IdChanges) {
+        if changes.is_empty() {
+            return;
+        }
+        let ids = &changes.games;
+        for id in self.collapsed_categories.iter_mut().flatten() {
+            *id = changes.categories.get(id).copied().unwrap_or(*id);
+        }
+        let remap = |id: &mut GameId| {
+            if let Some(new_id) = ids.get(id) {
+                *id = *new_id;
+            }
+        };
+
+        for id in [
+            &mut self.editing_name,
+            &mut self.show_sessions_window,
+            &mut self.show_confirm_delete_window,
+            &mut self.selected_game,
+            &mut self.show_game_details,
+            &mut self.csv_export.options.game_id,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            remap(id);
+        }
+        if let Some(draft) = &mut self.merge_games_draft {
+            draft.donor_id.iter_mut().chain(draft.target_id.iter_mut()).for_each(remap);
+        }
+        if let Some(draft) = &mut self.game_details_draft {
+            remap(&mut draft.game_id);
+        }
+        self.session_note_draft = self.session_note_draft.take().filter(|d| !d.session_id.is_nil());
+        self.session_split_draft = self.session_split_draft.take().filter(|d| !d.session_id.is_nil());
+
+        // Kept overlaps are "game|date|game|date"
+        for key in &mut self.kept_overlaps {
+            let parts: Vec<String> = key
+                .split('|')
+                .map(|part| match part.parse() {
+                    Ok(old) => ids.get(&GameId::legacy(old)).map_or(part.to_string(), |id| id.to_string()),
+                    Err(_) => part.to_string(),
+                })
+                .collect();
+            *key = parts.join("|");
         }
     }
 }
 
 impl TimeTrackerApp {
     pub fn new(cc: &CreationContext<'_>) -> Self {
-        let state: PersistedState =
+        let mut state: PersistedState =
             cc.storage.and_then(|storage| eframe::get_value(storage, eframe::APP_KEY)).unwrap_or_default();
+        sync_storage::set_device_id(&state.device_id);
+        match json_storage::migrate_legacy_ids() {
+            Ok(changes) => state.remap_ids(&changes),
+            Err(err) => eprintln!("Failed to migrate legacy ids: {}", err),
+        }
+        match sync_storage::pick_up_sync_files() {
+            Ok(report) if report.files > 0 || !report.skipped.is_empty() => {
+                state.sync_message = Some(report.describe());
+                state.show_sync_window = true;
+            }
+            Ok(_) => {}
+            Err(err) => state.sync_message = Some(err),
+        }
+        session_crud::backfill_last_played();
+
+        // The files may have been changed by another device since the state was saved
+        state.games = json_storage::load_games();
+        state.games.sort_by_key(|g| g.sort_index);
+        state.categories = json_storage::load_categories();
+        state.goals = json_storage::load_goals();
+        state.limits = json_storage::load_limits();
 
         if state.dark_mode {
             cc.egui_ctx.set_visuals(egui::Visuals::dark());
@@ -55,7 +411,19 @@ impl TimeTrackerApp {
 
         let (tx, rx) = channel::<GameUpdate>();
 
-        Self { state, updates_tx: tx, updates_rx: rx }
+        let missing_games = relink::find_missing_games(&state.games);
+
+        Self {
+            state,
+            updates_tx: tx,
+            updates_rx: rx,
+            missing_games,
+            cover_textures: HashMap::new(),
+            reminders: Vec::new(),
+            pending_restore: None,
+            library_merge: None,
+            goal_header: None,
+        }
     }
 }
 
@@ -66,6 +434,24 @@ impl App for TimeTrackerApp {
             ui::add_game_window::draw_add_game_window(self, ctx);
             ui::show_sessions_window::draw_sessions_window(self, ctx);
             ui::delete_game_window::draw_confirm_delete_window(self, ctx);
+            ui::session_note_window::draw_session_note_window(self, ctx);
+            ui::overlaps_window::draw_overlaps_window(self, ctx);
+            ui::merge_games_window::draw_merge_games_window(self, ctx);
+            ui::relink_window::draw_relink_window(self, ctx);
+            ui::categories_window::draw_categories_window(self, ctx);
+            ui::game_details_window::draw_game_details_window(self, ctx);
+            ui::tags_window::draw_tags_window(self, ctx);
+            ui::statistics_window::draw_statistics_window(self, ctx);
+            ui::heatmap_window::draw_heatmap_window(self, ctx);
+            ui::goals_window::draw_goals_window(self, ctx);
+            ui::limits_window::draw_limits_window(self, ctx);
+            ui::limits_window::draw_reminders(self, ctx);
+            ui::year_review_window::draw_year_review_window(self, ctx);
+            ui::export_window::draw_export_window(self, ctx);
+            ui::import_window::draw_import_window(self, ctx);
+            ui::backup_window::draw_backup_window(self, ctx);
+            ui::library_merge_window::draw_library_merge_window(self, ctx);
+            ui::sync_window::draw_sync_window(self, ctx);
         });
     }
 
@@ -80,7 +466,7 @@ pub fn run_gui() -> eframe::Result<()> {
 
     let options = NativeOptions {
         viewport: egui::ViewportBuilder {
-            inner_size: Some([700.0, 250.0].into()),
+            inner_size: Some([850.0, 300.0].into()),
             min_inner_size: Some([700.0, 250.0].into()),
             icon: Some(icon),
             ..Default::default()
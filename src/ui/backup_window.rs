@@ -0,0 +1,162 @@
+use crate::core::backup::{RestoreMode, create_backup, read_backup, restore_backup};
+use crate::core::json_storage::{load_categories, load_games, load_goals, load_limits, migrate_legacy_ids};
+use crate::core::relink::find_missing_games;
+use crate::ui::app::{PendingRestore, PersistedState, Settings, TimeTrackerApp};
+use crate::ui::ui_patterns::{ButtonStyle, action_button};
+use chrono::Local;
+use eframe::egui::{self, Grid, RichText, Ui, Vec2, Visuals};
+use rfd::FileDialog;
+
+/// Asks for a file and writes a backup of everything. Returns the message to show.
+fn export_backup(state: &PersistedState) -> Option<String> {
+    let path = FileDialog::new()
+        .set_file_name(format!("vn-time-tracker-{}.zip", Local::now().format("%Y-%m-%d")))
+        .add_filter("Backup", &["zip"])
+        .save_file()?;
+
+    let settings = match serde_json::to_string(&state.settings()) {
+        Ok(settings) => settings,
+        Err(err) => return Some(format!("Can't save settings: {}", err)),
+    };
+    Some(match create_backup(&path, &settings) {
+        Ok(manifest) => format!("Saved {} files to {}", manifest.files.len(), path.display()),
+        Err(err) => err,
+    })
+}
+
+/// Asks for a backup archive and checks it. The restore waits for confirmation.
+fn open_backup(app: &mut TimeTrackerApp) {
+    let Some(path) = FileDialog::new().add_filter("Backup", &["zip"]).pick_file() else {
+        return;
+    };
+
+    match read_backup(&path) {
+        Ok(contents) => {
+            app.pending_restore =
+                Some(PendingRestore { path: path.display().to_string(), contents, mode: RestoreMode::default() });
+            app.state.backup_message = None;
+        }
+        Err(err) => app.state.backup_message = Some(err),
+    }
+}
+
+/// What the opened backup holds and how to restore it.
+fn draw_pending_restore(ui: &mut Ui, pending: &mut PendingRestore) {
+    let contents = &pending.contents;
+
+    ui.label(RichText::new(&pending.path).size(13.0).weak());
+    Grid::new("backup_contents").num_columns(2).spacing([14.0, 4.0]).show(ui, |ui| {
+        for (label, value) in [
+            ("Created:", contents.manifest.created.clone()),
+            ("Tracker version:", contents.manifest.app_version.clone()),
+            ("Games:", contents.games.len().to_string()),
+            ("Sessions:", contents.sessions.len().to_string()),
+            ("Covers:", contents.covers.len().to_string()),
+        ] {
+            ui.label(RichText::new(label).size(15.0));
+            ui.label(RichText::new(value).size(15.0).strong());
+            ui.end_row();
+        }
+    });
+
+    ui.add_space(6.0);
+    for mode in RestoreMode::ALL {
+        ui.radio_value(&mut pending.mode, mode, mode.label());
+    }
+    let hint = match pending.mode {
+        RestoreMode::Replace => "Current games, sessions and settings are overwritten",
+        RestoreMode::Merge => "Games are matched by id, path or name, sessions already here are skipped",
+    };
+    ui.label(RichText::new(hint).size(13.0).weak());
+}
+
+/// Reloads everything cached from the data files after a restore.
+/// Backups of older versions still have numeric game ids, which are replaced first.
+fn reload_after_restore(app: &mut TimeTrackerApp) {
+    match migrate_legacy_ids() {
+        Ok(changes) => app.state.remap_ids(&changes),
+        Err(err) => eprintln!("Failed to migrate legacy ids: {}", err),
+    }
+    app.state.games = load_games();
+    app.state.categories = load_categories();
+    app.state.goals = load_goals();
+    app.state.limits = load_limits();
+    app.missing_games = find_missing_games(&app.state.games);
+    app.cover_textures.clear();
+    app.goal_header = None;
+}
+
+/// Renders the window for exporting a backup archive and restoring one.
+pub fn draw_backup_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_backup_window {
+        return;
+    }
+
+    let mut window_open = true;
+    let mut export_clicked = false;
+    let mut open_clicked = false;
+    let mut restore_clicked = false;
+    let mut cancel_clicked = false;
+
+    egui::Window::new("Backup & restore").resizable(false).open(&mut window_open).show(ctx, |ui| {
+        ui.label(RichText::new("One archive with games, sessions, settings and covers").size(14.0));
+        ui.horizontal(|ui| {
+            export_clicked =
+                action_button(ui, "💾 Export backup…", Vec2::new(140.0, 26.0), Some(ButtonStyle::Success)).clicked();
+            open_clicked = action_button(ui, "📂 Restore backup…", Vec2::new(140.0, 26.0), None).clicked();
+        });
+
+        if let Some(pending) = app.pending_restore.as_mut() {
+            ui.separator();
+            draw_pending_restore(ui, pending);
+            ui.horizontal(|ui| {
+                restore_clicked =
+                    action_button(ui, "Restore", Vec2::new(80.0, 25.0), Some(ButtonStyle::Danger)).clicked();
+                cancel_clicked = action_button(ui, "Cancel", Vec2::new(80.0, 25.0), None).clicked();
+            });
+        }
+
+        if let Some(message) = &app.state.backup_message {
+            ui.separator();
+            ui.label(RichText::new(message).size(14.0));
+        }
+    });
+
+    if export_clicked && let Some(message) = export_backup(&app.state) {
+        app.state.backup_message = Some(message);
+    }
+    if open_clicked {
+        open_backup(app);
+    }
+    if cancel_clicked {
+        app.pending_restore = None;
+    }
+    if restore_clicked && let Some(pending) = app.pending_restore.take() {
+        match restore_backup(&pending.contents, pending.mode) {
+            Ok(summary) => {
+                // Backups of older versions hold the whole window state, only the settings are read from it
+                if pending.mode == RestoreMode::Replace
+                    && let Some(settings) = pending.contents.settings.as_deref()
+                    && let Ok(settings) = serde_json::from_str::<Settings>(settings)
+                {
+                    app.state.apply_settings(settings);
+                    ctx.set_visuals(if app.state.dark_mode { Visuals::dark() } else { Visuals::light() });
+                }
+                reload_after_restore(app);
+                app.state.backup_message = Some(match pending.mode {
+                    RestoreMode::Replace => {
+                        format!("Restored {} games and {} sessions", summary.games, summary.sessions)
+                    }
+                    RestoreMode::Merge => format!("Added {} games and {} sessions", summary.games, summary.sessions),
+                });
+            }
+            Err(err) => app.state.backup_message = Some(err),
+        }
+    }
+
+    if !window_open {
+        app.state.show_backup_window = false;
+        app.state.backup_message = None;
+        app.pending_restore = None;
+    }
+}
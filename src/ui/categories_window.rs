@@ -0,0 +1,85 @@
+use crate::core::category_crud::{add_category, delete_category, rename_category};
+use crate::core::json_storage::{load_categories, load_games};
+use crate::ui::app::TimeTrackerApp;
+use crate::ui::ui_patterns::{ButtonStyle, action_button, labeled_text_edit};
+use eframe::egui::{self, Color32, FontId, RichText, ScrollArea, TextEdit, Vec2};
+
+/// Renders the window for creating, renaming and deleting categories.
+pub fn draw_categories_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_categories_window {
+        return;
+    }
+
+    let mut window_open = true;
+    let mut renamed = None;
+    let mut deleted = None;
+    let mut added = false;
+
+    egui::Window::new("Categories").resizable(true).default_size(Vec2::new(320.0, 240.0)).open(&mut window_open).show(
+        ctx,
+        |ui| {
+            // --- New category ---
+            ui.horizontal(|ui| {
+                labeled_text_edit(ui, "New:", &mut app.state.new_category_name, 16.0, Vec2::new(180.0, 24.0), 16.0);
+                if action_button(ui, "➕", Vec2::new(30.0, 24.0), Some(ButtonStyle::Success)).clicked() {
+                    added = true;
+                }
+            });
+
+            ui.separator();
+
+            // --- Existing categories ---
+            ScrollArea::vertical().show(ui, |ui| {
+                if app.state.categories.is_empty() {
+                    ui.label(RichText::new("No categories yet").size(16.0));
+                }
+
+                for category in &mut app.state.categories {
+                    ui.horizontal(|ui| {
+                        let response = ui.add(
+                            TextEdit::singleline(&mut category.name)
+                                .font(FontId::proportional(16.0))
+                                .desired_width(220.0)
+                                .background_color(Color32::from_rgb(200, 200, 200))
+                                .text_color(Color32::from_rgb(40, 40, 40)),
+                        );
+                        if response.lost_focus() {
+                            renamed = Some((category.id, category.name.trim().to_string()));
+                        }
+
+                        if action_button(ui, "🗑", Vec2::new(30.0, 24.0), Some(ButtonStyle::Danger))
+                            .on_hover_text("Delete category, its games become uncategorized")
+                            .clicked()
+                        {
+                            deleted = Some(category.id);
+                        }
+                    });
+                }
+            });
+        },
+    );
+
+    let changed = added || renamed.is_some() || deleted.is_some();
+    let name = app.state.new_category_name.trim().to_string();
+    if added && !name.is_empty() {
+        add_category(name);
+        app.state.new_category_name.clear();
+    }
+    if let Some((category_id, name)) = renamed
+        && !name.is_empty()
+    {
+        rename_category(category_id, name);
+    }
+    if let Some(category_id) = deleted {
+        delete_category(category_id);
+        app.state.collapsed_categories.retain(|c| *c != Some(category_id));
+        app.state.games = load_games();
+    }
+    if changed {
+        app.state.categories = load_categories();
+    }
+
+    if !window_open {
+        app.state.show_categories_window = false;
+    }
+}
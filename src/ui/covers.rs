@@ -0,0 +1,47 @@
+use crate::core::covers::load_cover;
+use eframe::egui::{
+    Align2, Color32, ColorImage, Context, FontId, Image, Response, Sense, TextureHandle, TextureOptions, Ui, Vec2,
+};
+use std::collections::HashMap;
+
+/// Returns the texture of a cover thumbnail, loading it on first use.
+pub fn cover_texture(
+    textures: &mut HashMap<String, Option<TextureHandle>>,
+    ctx: &Context,
+    cover: &str,
+) -> Option<TextureHandle> {
+    if cover.is_empty() {
+        return None;
+    }
+
+    textures
+        .entry(cover.to_string())
+        .or_insert_with(|| {
+            let image = load_cover(cover)?;
+            let size = [image.width() as usize, image.height() as usize];
+            let pixels = ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+            Some(ctx.load_texture(cover, pixels, TextureOptions::LINEAR))
+        })
+        .clone()
+}
+
+/// Draws a cover scaled to fit `size`, or a placeholder box if there is none.
+pub fn draw_cover(ui: &mut Ui, texture: Option<&TextureHandle>, size: Vec2) -> Response {
+    match texture {
+        Some(texture) => ui.add(Image::new(texture).fit_to_exact_size(size).sense(Sense::click())),
+        None => {
+            let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+            ui.painter().rect_filled(rect, 4.0, Color32::from_gray(128).gamma_multiply(0.3));
+            if size.y >= 48.0 {
+                ui.painter().text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    "🖼",
+                    FontId::proportional(size.y / 4.0),
+                    Color32::from_gray(128),
+                );
+            }
+            response
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::core::csv_export::{
+    DurationFormat, ExportKind, GameColumn, SessionColumn, export_csv, validate_date_format,
+};
+use crate::core::json_storage::Game;
+use crate::ui::app::{CsvExportState, TimeTrackerApp};
+use crate::ui::ui_patterns::{ButtonStyle, action_button, labeled_text_edit};
+use chrono::NaiveDate;
+use eframe::egui::{self, ComboBox, RichText, ScrollArea, Ui, Vec2};
+use rfd::FileDialog;
+
+/// Checkbox per column, keeping the selected ones in the order of `all`.
+fn draw_column_toggles<T: Copy + PartialEq>(
+    ui: &mut Ui,
+    selected: &mut Vec<T>,
+    all: &[T],
+    key: impl Fn(&T) -> &'static str,
+) {
+    ui.horizontal_wrapped(|ui| {
+        for column in all {
+            let mut checked = selected.contains(column);
+            if ui.checkbox(&mut checked, key(column)).changed() {
+                if checked {
+                    selected.push(*column);
+                } else {
+                    selected.retain(|c| c != column);
+                }
+                let order = |c: &T| all.iter().position(|a| a == c);
+                selected.sort_by_key(order);
+            }
+        }
+    });
+}
+
+/// Parses an optional `"YYYY-MM-DD"` bound. Empty means no bound.
+fn parse_bound(text: &str, label: &str) -> Result<Option<NaiveDate>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    text.parse().map(Some).map_err(|_| format!("{} date must be YYYY-MM-DD", label))
+}
+
+/// Asks for a file and writes the export. Returns the message to show.
+fn run_export(state: &mut CsvExportState) -> Option<String> {
+    let bounds = parse_bound(&state.from, "From").and_then(|from| Ok((from, parse_bound(&state.to, "To")?)));
+    let (from, to) = match bounds.and_then(|b| validate_date_format(&state.options.date_format).map(|_| b)) {
+        Ok(bounds) => bounds,
+        Err(err) => return Some(err),
+    };
+    state.options.from = from;
+    state.options.to = to;
+
+    let path = FileDialog::new()
+        .set_file_name(format!("{}.csv", state.options.kind.key()))
+        .add_filter("CSV", &["csv"])
+        .save_file()?;
+
+    Some(match export_csv(&path, &state.options) {
+        Ok(rows) => format!("Exported {} rows to {}", rows, path.display()),
+        Err(err) => err,
+    })
+}
+
+fn draw_options(ui: &mut Ui, state: &mut CsvExportState, games: &[Game]) {
+    let options = &mut state.options;
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Export:").size(15.0));
+        for kind in ExportKind::ALL {
+            ui.radio_value(&mut options.kind, kind, kind.label());
+        }
+    });
+
+    ui.label(RichText::new("Columns").size(15.0).strong());
+    match options.kind {
+        ExportKind::Sessions => draw_column_toggles(ui, &mut options.session_columns, &SessionColumn::ALL, |c| c.key()),
+        ExportKind::Games => draw_column_toggles(ui, &mut options.game_columns, &GameColumn::ALL, |c| c.key()),
+    }
+
+    ui.add_space(6.0);
+    labeled_text_edit(ui, "Date format:", &mut options.date_format, 15.0, Vec2::new(160.0, 22.0), 15.0);
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Durations:").size(15.0));
+        ComboBox::from_id_salt("csv_duration_format").selected_text(options.duration_format.label()).show_ui(
+            ui,
+            |ui| {
+                for format in DurationFormat::ALL {
+                    ui.selectable_value(&mut options.duration_format, format, format.label());
+                }
+            },
+        );
+    });
+
+    ui.add_space(6.0);
+    ui.label(RichText::new("Filter").size(15.0).strong());
+    ui.horizontal(|ui| {
+        labeled_text_edit(ui, "From:", &mut state.from, 15.0, Vec2::new(95.0, 22.0), 15.0);
+        labeled_text_edit(ui, "To:", &mut state.to, 15.0, Vec2::new(95.0, 22.0), 15.0);
+    });
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Game:").size(15.0));
+        let options = &mut state.options;
+        let selected =
+            options.game_id.and_then(|id| games.iter().find(|g| g.id == id)).map_or("All games", |g| &g.name);
+        ComboBox::from_id_salt("csv_game").selected_text(selected).show_ui(ui, |ui| {
+            ui.selectable_value(&mut options.game_id, None, "All games");
+            for game in games {
+                ui.selectable_value(&mut options.game_id, Some(game.id), &game.name);
+            }
+        });
+    });
+}
+
+/// Renders the CSV export of games and sessions.
+pub fn draw_export_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_export_window {
+        return;
+    }
+
+    let mut window_open = true;
+
+    egui::Window::new("Export CSV").resizable(true).default_size(Vec2::new(440.0, 360.0)).open(&mut window_open).show(
+        ctx,
+        |ui| {
+            ScrollArea::vertical().show(ui, |ui| {
+                draw_options(ui, &mut app.state.csv_export, &app.state.games);
+
+                ui.separator();
+                if action_button(ui, "💾 Export CSV…", Vec2::new(130.0, 26.0), Some(ButtonStyle::Success)).clicked()
+                    && let Some(message) = run_export(&mut app.state.csv_export)
+                {
+                    app.state.csv_export.message = Some(message);
+                }
+                if let Some(message) = &app.state.csv_export.message {
+                    ui.label(RichText::new(message).size(14.0));
+                }
+            });
+        },
+    );
+
+    if !window_open {
+        app.state.show_export_window = false;
+        app.state.csv_export.message = None;
+    }
+}
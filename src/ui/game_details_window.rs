@@ -0,0 +1,324 @@
+use crate::core::covers::{remove_cover, set_cover_from_executable, set_cover_from_file};
+use crate::core::duration::{format_date_time, format_duration};
+use crate::core::game_crud::{set_game_status, update_game_details};
+use crate::core::json_storage::{Game, GameId, LaunchSettings, PlayStatus, load_games, load_sessions};
+use crate::core::relink::find_missing_games;
+use crate::core::statistics::{playtime_per_month, summarize_sessions};
+use crate::core::tag_crud::{all_tags, normalize_tag, set_game_tags};
+use crate::ui::app::{GameDetailsDraft, TimeTrackerApp};
+use crate::ui::covers::{cover_texture, draw_cover};
+use crate::ui::ui_patterns::{ButtonStyle, action_button, bar_chart, labeled_text_edit, tag_chip};
+use eframe::egui::{
+    self, Color32, ComboBox, FontId, Grid, Key, RichText, ScrollArea, TextEdit, TextureHandle, Ui, Vec2,
+};
+use rfd::FileDialog;
+use std::path::PathBuf;
+
+impl GameDetailsDraft {
+    fn from_game(game: &Game) -> Self {
+        Self {
+            game_id: game.id,
+            name: game.name.clone(),
+            path: game.path.clone(),
+            arguments: game.launch.arguments.clone(),
+            working_dir: game.launch.working_dir.clone(),
+            notes: game.notes.clone(),
+            cover_error: None,
+        }
+    }
+}
+
+/// Single-line text field used for the editable details. Returns `true` when editing finished.
+fn details_text_edit(ui: &mut Ui, value: &mut String) -> bool {
+    ui.add(
+        TextEdit::singleline(value)
+            .font(FontId::proportional(15.0))
+            .desired_width(280.0)
+            .background_color(Color32::from_rgb(200, 200, 200))
+            .text_color(Color32::from_rgb(40, 40, 40)),
+    )
+    .lost_focus()
+}
+
+/// Draws name, path and launch settings. Returns `true` if anything should be saved.
+fn draw_editable_fields(ui: &mut Ui, draft: &mut GameDetailsDraft) -> bool {
+    let mut changed = false;
+
+    Grid::new("details_fields").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+        ui.label(RichText::new("Name:").size(16.0));
+        changed |= details_text_edit(ui, &mut draft.name);
+        ui.end_row();
+
+        ui.label(RichText::new("Path:").size(16.0));
+        ui.horizontal(|ui| {
+            changed |= details_text_edit(ui, &mut draft.path);
+            if action_button(ui, "📂", Vec2::new(24.0, 20.0), None).clicked()
+                && let Some(path) = FileDialog::new().pick_file()
+            {
+                draft.path = path.to_string_lossy().to_string();
+                changed = true;
+            }
+        });
+        ui.end_row();
+
+        ui.label(RichText::new("Arguments:").size(16.0));
+        changed |= details_text_edit(ui, &mut draft.arguments);
+        ui.end_row();
+
+        ui.label(RichText::new("Start in:").size(16.0));
+        ui.horizontal(|ui| {
+            changed |= details_text_edit(ui, &mut draft.working_dir);
+            if action_button(ui, "📂", Vec2::new(24.0, 20.0), None).clicked()
+                && let Some(dir) = FileDialog::new().pick_folder()
+            {
+                draft.working_dir = dir.to_string_lossy().to_string();
+                changed = true;
+            }
+        });
+        ui.end_row();
+    });
+
+    changed
+}
+
+/// Cover change requested in the window
+enum CoverEdit {
+    FromFile(PathBuf),
+    FromExecutable,
+    Remove,
+}
+
+/// Draws the cover with buttons to replace or remove it.
+fn draw_cover_section(ui: &mut Ui, cover: Option<&TextureHandle>, error: Option<&String>) -> Option<CoverEdit> {
+    let mut edit = None;
+
+    ui.horizontal(|ui| {
+        draw_cover(ui, cover, Vec2::splat(128.0));
+
+        ui.vertical(|ui| {
+            let size = Vec2::new(150.0, 24.0);
+            if action_button(ui, "🖼 From file…", size, Some(ButtonStyle::Neutral)).clicked()
+                && let Some(path) =
+                    FileDialog::new().add_filter("Images", &["png", "jpg", "jpeg", "bmp", "ico", "webp"]).pick_file()
+            {
+                edit = Some(CoverEdit::FromFile(path));
+            }
+            if action_button(ui, "⚙ From exe icon", size, Some(ButtonStyle::Neutral))
+                .on_hover_text("Use the icon embedded in the game executable")
+                .clicked()
+            {
+                edit = Some(CoverEdit::FromExecutable);
+            }
+            if cover.is_some() && action_button(ui, "✖ Remove", size, Some(ButtonStyle::Danger)).clicked() {
+                edit = Some(CoverEdit::Remove);
+            }
+            if let Some(error) = error {
+                ui.label(RichText::new(error).size(14.0).color(ButtonStyle::Danger.color()));
+            }
+        });
+    });
+
+    edit
+}
+
+/// Draws playtime numbers and a per-month timeline of the game.
+fn draw_play_summary(ui: &mut Ui, game_id: GameId) {
+    let sessions = load_sessions(game_id);
+    let summary = summarize_sessions(&sessions);
+    let date = |d: Option<chrono::NaiveDateTime>| d.map(format_date_time).unwrap_or_else(|| "—".to_string());
+
+    Grid::new("details_summary").num_columns(2).spacing([10.0, 4.0]).show(ui, |ui| {
+        for (label, value) in [
+            ("First played:", date(summary.first_played)),
+            ("Last played:", date(summary.last_played)),
+            ("Sessions:", summary.count.to_string()),
+            ("Total time:", format_duration(summary.total_secs)),
+            ("Average session:", format_duration(summary.average_secs)),
+            ("Longest session:", format_duration(summary.longest_secs)),
+        ] {
+            ui.label(RichText::new(label).size(15.0));
+            ui.label(RichText::new(value).size(15.0));
+            ui.end_row();
+        }
+    });
+
+    let months = playtime_per_month(&sessions);
+    if !months.is_empty() {
+        ui.label(RichText::new("Playtime per month").size(14.0).weak());
+        bar_chart(ui, &months, 60.0, format_duration);
+    }
+}
+
+/// Renders the details window of one game. Every field is edited in place and saved on change.
+pub fn draw_game_details_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    let Some(game_id) = app.state.show_game_details else {
+        return;
+    };
+    let Some(game) = app.state.games.iter().find(|g| g.id == game_id).cloned() else {
+        app.state.show_game_details = None;
+        return;
+    };
+
+    // Start editing a copy when another game was opened
+    if app.state.game_details_draft.as_ref().is_none_or(|d| d.game_id != game_id) {
+        app.state.game_details_draft = Some(GameDetailsDraft::from_game(&game));
+    }
+
+    let mut window_open = true;
+    let mut new_tags = None;
+    let mut new_status = None;
+    let mut save_fields = false;
+    let mut cover_edit = None;
+    let cover = cover_texture(&mut app.cover_textures, ctx, &game.cover);
+
+    egui::Window::new(format!("Details: {}", game.name))
+        .id(egui::Id::new("game_details"))
+        .resizable(true)
+        .default_size(Vec2::new(420.0, 520.0))
+        .open(&mut window_open)
+        .show(ctx, |ui| {
+            ScrollArea::vertical().show(ui, |ui| {
+                // --- Cover ---
+                let error = app.state.game_details_draft.as_ref().and_then(|d| d.cover_error.as_ref());
+                cover_edit = draw_cover_section(ui, cover.as_ref(), error);
+
+                ui.separator();
+
+                // --- Name, path, launch settings ---
+                if let Some(draft) = app.state.game_details_draft.as_mut() {
+                    save_fields |= draw_editable_fields(ui, draft);
+                }
+
+                ui.separator();
+
+                // --- Status ---
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Status:").size(18.0).strong());
+                    let selected = format!("{} {}", game.status.icon(), game.status.label());
+                    ComboBox::from_id_salt("details_status").selected_text(selected).show_ui(ui, |ui| {
+                        for status in PlayStatus::ALL {
+                            let text = format!("{} {}", status.icon(), status.label());
+                            if ui.selectable_label(game.status == status, text).clicked() {
+                                new_status = Some(status);
+                            }
+                        }
+                    });
+                });
+                for change in game.status_history.iter().rev() {
+                    ui.label(RichText::new(format!("{}  {}", change.date, change.status.label())).size(13.0).weak());
+                }
+
+                ui.separator();
+
+                // --- Tags ---
+                ui.label(RichText::new("Tags").size(18.0).strong());
+
+                ui.horizontal_wrapped(|ui| {
+                    if game.tags.is_empty() {
+                        ui.label(RichText::new("No tags").size(14.0).italics());
+                    }
+                    for tag in &game.tags {
+                        if tag_chip(ui, &format!("{} ✖", tag), false).on_hover_text("Remove tag").clicked() {
+                            new_tags = Some(game.tags.iter().filter(|t| *t != tag).cloned().collect::<Vec<_>>());
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    labeled_text_edit(ui, "Add:", &mut app.state.new_tag_name, 16.0, Vec2::new(160.0, 24.0), 16.0);
+
+                    let enter = ui.input(|i| i.key_pressed(Key::Enter));
+                    let add = action_button(ui, "➕", Vec2::new(30.0, 24.0), Some(ButtonStyle::Success)).clicked();
+                    if (add || enter)
+                        && let Some(tag) = normalize_tag(&app.state.new_tag_name)
+                    {
+                        let mut tags = game.tags.clone();
+                        tags.push(tag);
+                        new_tags = Some(tags);
+                        app.state.new_tag_name.clear();
+                    }
+
+                    // Existing tags of the library the game doesn't have yet
+                    let known: Vec<String> = all_tags(&app.state.games)
+                        .into_iter()
+                        .map(|(t, _)| t)
+                        .filter(|t| !game.tags.contains(t))
+                        .collect();
+                    if !known.is_empty() {
+                        ComboBox::from_id_salt("details_known_tags").selected_text("Existing…").show_ui(ui, |ui| {
+                            for tag in known {
+                                if ui.selectable_label(false, &tag).clicked() {
+                                    let mut tags = game.tags.clone();
+                                    tags.push(tag);
+                                    new_tags = Some(tags);
+                                }
+                            }
+                        });
+                    }
+                });
+
+                ui.separator();
+
+                // --- Notes ---
+                ui.label(RichText::new("Notes").size(18.0).strong());
+                if let Some(draft) = app.state.game_details_draft.as_mut() {
+                    save_fields |= ui
+                        .add(
+                            TextEdit::multiline(&mut draft.notes)
+                                .font(FontId::proportional(15.0))
+                                .desired_rows(3)
+                                .desired_width(f32::INFINITY)
+                                .background_color(Color32::from_rgb(200, 200, 200))
+                                .text_color(Color32::from_rgb(40, 40, 40)),
+                        )
+                        .lost_focus();
+                }
+
+                ui.separator();
+
+                // --- Play summary ---
+                ui.label(RichText::new("Playtime").size(18.0).strong());
+                draw_play_summary(ui, game_id);
+            });
+        });
+
+    if save_fields && let Some(draft) = app.state.game_details_draft.clone() {
+        let name = if draft.name.trim().is_empty() { game.name.clone() } else { draft.name.trim().to_string() };
+        let launch = LaunchSettings {
+            arguments: draft.arguments.trim().to_string(),
+            working_dir: draft.working_dir.trim().to_string(),
+        };
+        update_game_details(game_id, name, draft.path.trim().to_string(), launch, draft.notes.clone());
+        app.state.games = load_games();
+        app.missing_games = find_missing_games(&app.state.games);
+    }
+    if let Some(edit) = cover_edit {
+        let result = match edit {
+            CoverEdit::FromFile(path) => set_cover_from_file(game_id, &path),
+            CoverEdit::FromExecutable => set_cover_from_executable(game_id),
+            CoverEdit::Remove => {
+                remove_cover(game_id);
+                Ok(())
+            }
+        };
+        app.cover_textures.remove(&game.cover);
+        if let Some(draft) = app.state.game_details_draft.as_mut() {
+            draft.cover_error = result.err();
+        }
+        app.state.games = load_games();
+    }
+    if let Some(tags) = new_tags {
+        set_game_tags(game_id, tags);
+        app.state.games = load_games();
+    }
+    if let Some(status) = new_status {
+        set_game_status(game_id, status);
+        app.state.games = load_games();
+    }
+
+    if !window_open {
+        app.state.show_game_details = None;
+        app.state.game_details_draft = None;
+        app.state.new_tag_name.clear();
+    }
+}
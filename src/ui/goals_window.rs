@@ -0,0 +1,122 @@
+use crate::core::duration::format_duration;
+use crate::core::goals::{add_goal, delete_goal, goal_progress, goal_streak, update_goal};
+use crate::core::json_storage::{Game, GoalPeriod, PlayGoal, Session, load_all_sessions, load_goals};
+use crate::ui::app::TimeTrackerApp;
+use crate::ui::ui_patterns::{ButtonStyle, action_button};
+use chrono::Local;
+use eframe::egui::{self, ComboBox, DragValue, ProgressBar, RichText, ScrollArea, Ui, Vec2};
+
+/// Describes a goal, e.g. `"0h 30m 0s a day · All games"`.
+pub fn describe_goal(goal: &PlayGoal, games: &[Game]) -> String {
+    let target = match goal.game_id {
+        Some(id) => games.iter().find(|g| g.id == id).map_or("Unknown game", |g| g.name.as_str()),
+        None => "All games",
+    };
+    format!("{} {} · {}", format_duration(goal.minutes * 60), goal.period.label(), target)
+}
+
+/// Draws how far the goal is in the current period, plus its streaks.
+pub fn draw_goal_progress(ui: &mut Ui, goal: &PlayGoal, sessions: &[Session]) {
+    let today = Local::now().date_naive();
+    let played = goal_progress(goal, sessions, today);
+    let target = (goal.minutes * 60).max(1);
+    let streak = goal_streak(goal, sessions, today);
+
+    ui.horizontal(|ui| {
+        let met = if played >= target { " ✔" } else { "" };
+        let text = format!("{} / {}{}", format_duration(played), format_duration(target), met);
+        ui.add(ProgressBar::new(played as f32 / target as f32).desired_width(220.0).text(text));
+        ui.label(RichText::new(format!("🔥 {}  best {}", streak.current, streak.longest)).size(14.0))
+            .on_hover_text("Current and longest streak of reached periods");
+    });
+}
+
+/// Draws the editable game, target and period of a goal. Returns `true` if anything changed.
+fn draw_goal_fields(ui: &mut Ui, goal: &mut PlayGoal, games: &[Game]) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        changed |= ui.add(DragValue::new(&mut goal.minutes).range(1..=10_000).suffix(" min")).changed();
+
+        ComboBox::from_id_salt(("goal_period", goal.id)).selected_text(goal.period.label()).show_ui(ui, |ui| {
+            for period in GoalPeriod::ALL {
+                changed |= ui.selectable_value(&mut goal.period, period, period.label()).changed();
+            }
+        });
+
+        let selected = goal.game_id.and_then(|id| games.iter().find(|g| g.id == id)).map_or("All games", |g| &g.name);
+        ComboBox::from_id_salt(("goal_game", goal.id)).selected_text(selected).show_ui(ui, |ui| {
+            changed |= ui.selectable_value(&mut goal.game_id, None, "All games").changed();
+            for game in games {
+                changed |= ui.selectable_value(&mut goal.game_id, Some(game.id), &game.name).changed();
+            }
+        });
+    });
+
+    changed
+}
+
+/// Renders the window for setting daily and weekly playtime goals.
+pub fn draw_goals_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_goals_window {
+        return;
+    }
+
+    let sessions = load_all_sessions();
+    let mut window_open = true;
+    let mut changed = None;
+    let mut deleted = None;
+    let mut added = false;
+
+    egui::Window::new("Goals").resizable(true).default_size(Vec2::new(440.0, 300.0)).open(&mut window_open).show(
+        ctx,
+        |ui| {
+            if action_button(ui, "➕ Add goal", Vec2::new(110.0, 24.0), Some(ButtonStyle::Success)).clicked() {
+                added = true;
+            }
+
+            ui.separator();
+
+            ScrollArea::vertical().show(ui, |ui| {
+                if app.state.goals.is_empty() {
+                    ui.label(RichText::new("No goals yet").size(16.0));
+                }
+
+                for goal in &mut app.state.goals {
+                    ui.group(|ui| {
+                        ui.horizontal(|ui| {
+                            if draw_goal_fields(ui, goal, &app.state.games) {
+                                changed = Some(goal.clone());
+                            }
+                            if action_button(ui, "🗑", Vec2::new(24.0, 20.0), Some(ButtonStyle::Danger))
+                                .on_hover_text("Delete goal")
+                                .clicked()
+                            {
+                                deleted = Some(goal.id);
+                            }
+                        });
+                        draw_goal_progress(ui, goal, &sessions);
+                    });
+                }
+            });
+        },
+    );
+
+    if added {
+        add_goal(None, GoalPeriod::Daily, 30);
+    }
+    if let Some(goal) = &changed {
+        update_goal(goal);
+    }
+    if let Some(goal_id) = deleted {
+        delete_goal(goal_id);
+    }
+    if added || changed.is_some() || deleted.is_some() {
+        app.state.goals = load_goals();
+        app.goal_header = None;
+    }
+
+    if !window_open {
+        app.state.show_goals_window = false;
+    }
+}
@@ -0,0 +1,196 @@
+use crate::core::duration::format_duration;
+use crate::core::goals::periods_met;
+use crate::core::json_storage::{Game, GameId, GoalPeriod, load_all_sessions};
+use crate::core::statistics::{DateRange, playtime_per_day_by_game};
+use crate::ui::app::TimeTrackerApp;
+use crate::ui::ui_patterns::{ButtonStyle, action_button};
+use chrono::{Datelike, Days, Local, NaiveDate};
+use eframe::egui::{self, Align2, Color32, FontId, Pos2, Rect, Response, RichText, ScrollArea, Sense, Ui, Vec2};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Side of one day square in pixels.
+const CELL: f32 = 13.0;
+/// Space between day squares.
+const GAP: f32 = 3.0;
+/// Room for weekday labels on the left and month labels on top.
+const LABELS: Vec2 = Vec2::new(32.0, 16.0);
+
+/// Colour of a day by minutes played: grey for none, then greener every step.
+fn day_color(secs: u64, dark_mode: bool) -> Color32 {
+    let empty = if dark_mode { Color32::from_gray(50) } else { Color32::from_gray(225) };
+    let level = match secs / 60 {
+        0 if secs == 0 => return empty,
+        0..15 => 0.25,
+        15..30 => 0.45,
+        30..60 => 0.65,
+        60..120 => 0.85,
+        _ => 1.0,
+    };
+    empty.lerp_to_gamma(ButtonStyle::Success.color(), level)
+}
+
+/// Name of a game by id, for tooltips.
+fn game_name(games: &[Game], game_id: GameId) -> &str {
+    games.iter().find(|g| g.id == game_id).map_or("Unknown game", |g| g.name.as_str())
+}
+
+/// Paints the year grid: one column per week, Monday on top. Days in `goal_days` get a dot.
+/// Returns the grid response and the day under the pointer.
+fn draw_year_grid(
+    ui: &mut Ui,
+    year: i32,
+    days: &BTreeMap<NaiveDate, Vec<(GameId, u64)>>,
+    selected: Option<NaiveDate>,
+    goal_days: &BTreeSet<NaiveDate>,
+) -> (Response, Option<NaiveDate>) {
+    let first = NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or_default();
+    let offset = first.weekday().num_days_from_monday();
+    let weeks = (first.leap_year() as u32 + 365 + offset).div_ceil(7);
+
+    let size = LABELS + Vec2::new(weeks as f32 * (CELL + GAP), 7.0 * (CELL + GAP));
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+    let painter = ui.painter_at(rect);
+    let origin = rect.min + LABELS;
+    let text_color = ui.visuals().weak_text_color();
+    let font = FontId::proportional(11.0);
+
+    for (row, label) in [(0, "Mon"), (2, "Wed"), (4, "Fri")] {
+        let pos = Pos2::new(rect.left(), origin.y + row as f32 * (CELL + GAP) + CELL / 2.0);
+        painter.text(pos, Align2::LEFT_CENTER, label, font.clone(), text_color);
+    }
+
+    // Cell of a day and its position in the grid
+    let cell_of = |day: NaiveDate| {
+        let index = day.ordinal0() + offset;
+        let min = origin + Vec2::new((index / 7) as f32 * (CELL + GAP), (index % 7) as f32 * (CELL + GAP));
+        Rect::from_min_size(min, Vec2::splat(CELL))
+    };
+
+    let mut hovered = None;
+    let mut day = first;
+    while day.year() == year {
+        let cell = cell_of(day);
+        if day.day() == 1 {
+            let pos = Pos2::new(cell.left(), rect.top());
+            painter.text(pos, Align2::LEFT_TOP, day.format("%b").to_string(), font.clone(), text_color);
+        }
+
+        let secs = days.get(&day).map_or(0, |games| games.iter().map(|(_, s)| s).sum());
+        painter.rect_filled(cell, 2.0, day_color(secs, ui.visuals().dark_mode));
+        if goal_days.contains(&day) {
+            painter.circle_filled(cell.center(), CELL / 5.0, ui.visuals().strong_text_color());
+        }
+        if selected == Some(day) {
+            painter.rect_stroke(cell, 2.0, (2.0, ui.visuals().strong_text_color()), egui::StrokeKind::Outside);
+        }
+        if response.hover_pos().is_some_and(|pos| cell.expand(GAP / 2.0).contains(pos)) {
+            hovered = Some(day);
+        }
+
+        let Some(next) = day.checked_add_days(Days::new(1)) else { break };
+        day = next;
+    }
+
+    (response, hovered)
+}
+
+/// Renders the GitHub-style calendar of daily playtime for one year.
+pub fn draw_heatmap_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_heatmap_window {
+        return;
+    }
+
+    let year = app.state.heatmap_year.unwrap_or_else(|| Local::now().year());
+    let first = NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or_default();
+    let last = NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or_default();
+    let sessions = load_all_sessions();
+    let days = playtime_per_day_by_game(&sessions, &DateRange::days(first, last));
+    // Days the library-wide daily goal was reached
+    let goal = app.state.goals.iter().find(|g| g.game_id.is_none() && g.period == GoalPeriod::Daily);
+    let goal_days = goal.map(|g| periods_met(g, &sessions)).unwrap_or_default();
+    let selected = app.state.heatmap_selected_day.as_deref().and_then(|d| d.parse::<NaiveDate>().ok());
+
+    let mut window_open = true;
+    let mut open_sessions = None;
+
+    egui::Window::new("Calendar").resizable(true).default_size(Vec2::new(900.0, 260.0)).open(&mut window_open).show(
+        ctx,
+        |ui| {
+            ui.horizontal(|ui| {
+                if action_button(ui, "⏴", Vec2::new(24.0, 24.0), None).clicked() {
+                    app.state.heatmap_year = Some(year - 1);
+                }
+                ui.label(RichText::new(year.to_string()).size(18.0).strong());
+                if action_button(ui, "⏵", Vec2::new(24.0, 24.0), None).clicked() {
+                    app.state.heatmap_year = Some(year + 1);
+                }
+
+                let total: u64 = days.values().flatten().map(|(_, secs)| secs).sum();
+                ui.add_space(20.0);
+                ui.label(
+                    RichText::new(format!("{} in {} active day(s)", format_duration(total), days.len())).size(16.0),
+                );
+            });
+
+            ui.separator();
+
+            ScrollArea::horizontal().show(ui, |ui| {
+                let (response, hovered) = draw_year_grid(ui, year, &days, selected, &goal_days);
+
+                if let Some(day) = hovered {
+                    if response.clicked() {
+                        app.state.heatmap_selected_day = Some(day.to_string());
+                    }
+                    response.on_hover_ui_at_pointer(|ui| {
+                        ui.label(RichText::new(day.format("%A, %Y-%m-%d").to_string()).strong());
+                        match days.get(&day) {
+                            Some(games) => {
+                                for (game_id, secs) in games {
+                                    ui.label(format!(
+                                        "{}: {}",
+                                        game_name(&app.state.games, *game_id),
+                                        format_duration(*secs)
+                                    ));
+                                }
+                            }
+                            None => {
+                                ui.label("Nothing played");
+                            }
+                        }
+                        if goal_days.contains(&day) {
+                            ui.label("🎯 Daily goal reached");
+                        }
+                    });
+                }
+            });
+
+            // --- Sessions of the clicked day ---
+            if let Some(day) = selected
+                && let Some(games) = days.get(&day)
+            {
+                ui.separator();
+                ui.label(RichText::new(day.format("%A, %Y-%m-%d").to_string()).size(16.0).strong());
+                for (game_id, secs) in games {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(game_name(&app.state.games, *game_id)).size(15.0));
+                        ui.label(RichText::new(format_duration(*secs)).size(15.0));
+                        if action_button(ui, "Sessions", Vec2::new(70.0, 20.0), Some(ButtonStyle::Neutral)).clicked() {
+                            open_sessions = Some((*game_id, day));
+                        }
+                    });
+                }
+            }
+        },
+    );
+
+    // Show the game's sessions narrowed down to that day
+    if let Some((game_id, day)) = open_sessions {
+        app.state.show_sessions_window = Some(game_id);
+        app.state.session_search = day.to_string();
+    }
+
+    if !window_open {
+        app.state.show_heatmap_window = false;
+        app.state.heatmap_selected_day = None;
+    }
+}
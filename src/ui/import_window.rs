@@ -0,0 +1,213 @@
+use crate::core::csv_import::{ImportField, ImportPlan, apply_import, guess_mapping, parse_csv, plan_import};
+use crate::core::duration::{format_date_time, format_duration};
+use crate::core::json_storage::{load_all_sessions, load_games};
+use crate::core::relink::find_missing_games;
+use crate::ui::app::{CsvImportDraft, TimeTrackerApp};
+use crate::ui::ui_patterns::{ButtonStyle, action_button, labeled_text_edit};
+use eframe::egui::{self, ComboBox, Grid, RichText, ScrollArea, Ui, Vec2};
+use rfd::FileDialog;
+use std::fs;
+
+/// Rows shown in the file and session previews.
+const PREVIEW_ROWS: usize = 8;
+
+/// Asks for a CSV file and starts the import wizard with it.
+pub fn open_csv_import(app: &mut TimeTrackerApp) {
+    let Some(path) = FileDialog::new().add_filter("CSV", &["csv", "tsv", "txt"]).pick_file() else {
+        return;
+    };
+
+    let mut draft = CsvImportDraft { path: path.display().to_string(), ..Default::default() };
+    match fs::read(&path) {
+        Ok(bytes) => {
+            draft.rows = parse_csv(&String::from_utf8_lossy(&bytes));
+            draft.options.mapping = guess_mapping(&draft.rows, draft.options.has_header);
+        }
+        Err(err) => draft.error = Some(format!("Can't read {}: {}", draft.path, err)),
+    }
+    app.state.csv_import_draft = Some(draft);
+}
+
+/// First rows of the file with a field picker above each column.
+fn draw_mapping(ui: &mut Ui, draft: &mut CsvImportDraft) {
+    ui.horizontal(|ui| {
+        if ui.checkbox(&mut draft.options.has_header, "First row is a header").changed() {
+            draft.options.mapping = guess_mapping(&draft.rows, draft.options.has_header);
+        }
+        ui.checkbox(&mut draft.options.numbers_in_hours, "Plain number durations are hours")
+            .on_hover_text("Otherwise they are seconds. H:MM:SS and \"1h 30m 0s\" are always recognised");
+    });
+    labeled_text_edit(ui, "Date format:", &mut draft.options.date_format, 15.0, Vec2::new(160.0, 22.0), 15.0);
+    ui.label(
+        RichText::new("strftime format, e.g. %d.%m.%Y %H:%M. Leave empty to recognise common formats")
+            .size(12.0)
+            .weak(),
+    );
+
+    ui.add_space(6.0);
+    ui.label(RichText::new("Map columns").size(15.0).strong());
+
+    ScrollArea::horizontal().show(ui, |ui| {
+        Grid::new("csv_import_preview").striped(true).spacing([10.0, 4.0]).show(ui, |ui| {
+            for (i, field) in draft.options.mapping.iter_mut().enumerate() {
+                ComboBox::from_id_salt(("csv_import_field", i)).selected_text(field.label()).show_ui(ui, |ui| {
+                    for option in ImportField::ALL {
+                        ui.selectable_value(field, option, option.label());
+                    }
+                });
+            }
+            ui.end_row();
+
+            for row in draft.rows.iter().take(PREVIEW_ROWS) {
+                for i in 0..draft.options.mapping.len() {
+                    let value = row.get(i).map_or("", |v| v.as_str());
+                    ui.label(RichText::new(value.lines().next().unwrap_or_default()).size(13.0));
+                }
+                ui.end_row();
+            }
+        });
+    });
+    ui.label(RichText::new(format!("{} rows in file", draft.rows.len())).size(13.0).weak());
+}
+
+/// Summary of what the import will do.
+fn draw_review(ui: &mut Ui, plan: &ImportPlan) {
+    let importable = plan.importable().count();
+
+    Grid::new("csv_import_summary").num_columns(2).spacing([14.0, 4.0]).show(ui, |ui| {
+        for (label, value) in [
+            ("Sessions to import:", importable),
+            ("Duplicates skipped:", plan.duplicate_count()),
+            ("New games:", plan.new_games.len()),
+            ("Rows with errors:", plan.errors.len()),
+        ] {
+            ui.label(RichText::new(label).size(15.0));
+            ui.label(RichText::new(value.to_string()).size(15.0).strong());
+            ui.end_row();
+        }
+    });
+
+    if !plan.new_games.is_empty() {
+        ui.add_space(6.0);
+        ui.label(RichText::new("Games to create").size(15.0).strong());
+        ui.label(RichText::new(plan.new_games.join(", ")).size(14.0));
+    }
+
+    if !plan.errors.is_empty() {
+        ui.add_space(6.0);
+        ui.label(RichText::new("Skipped rows").size(15.0).strong());
+        for (row, err) in plan.errors.iter().take(PREVIEW_ROWS) {
+            ui.label(RichText::new(format!("Row {}: {}", row, err)).size(13.0).color(ButtonStyle::Danger.color()));
+        }
+        if plan.errors.len() > PREVIEW_ROWS {
+            ui.label(RichText::new(format!("…and {} more", plan.errors.len() - PREVIEW_ROWS)).size(13.0).weak());
+        }
+    }
+
+    ui.add_space(6.0);
+    ui.label(RichText::new("Preview").size(15.0).strong());
+    Grid::new("csv_import_sessions").striped(true).spacing([10.0, 4.0]).show(ui, |ui| {
+        for session in plan.sessions.iter().take(PREVIEW_ROWS) {
+            let status = match (session.duplicate, session.game_id) {
+                (true, _) => "duplicate",
+                (false, Some(_)) => "new session",
+                (false, None) => "new game",
+            };
+            ui.label(RichText::new(format!("Row {}", session.row)).size(13.0).weak());
+            ui.label(RichText::new(&session.game_name).size(13.0));
+            ui.label(RichText::new(format_date_time(session.end)).size(13.0));
+            ui.label(RichText::new(format_duration(session.secs)).size(13.0));
+            ui.label(RichText::new(status).size(13.0).weak());
+            ui.end_row();
+        }
+    });
+}
+
+/// Renders the wizard importing sessions from a CSV file: mapping with preview, then review and import.
+pub fn draw_import_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    let Some(draft) = app.state.csv_import_draft.as_mut() else {
+        return;
+    };
+
+    let mut window_open = true;
+    let mut imported = false;
+    let mut close = false;
+
+    egui::Window::new("Import CSV").resizable(true).default_size(Vec2::new(560.0, 420.0)).open(&mut window_open).show(
+        ctx,
+        |ui| {
+            ui.label(RichText::new(&draft.path).size(13.0).weak());
+            ui.separator();
+
+            if let Some(message) = &draft.finished {
+                ui.label(RichText::new(message).size(16.0));
+                close = action_button(ui, "Close", Vec2::new(80.0, 25.0), None).clicked();
+                return;
+            }
+
+            let plan = draft
+                .reviewing
+                .then(|| plan_import(&draft.rows, &draft.options, &app.state.games, &load_all_sessions()));
+
+            ScrollArea::vertical().max_height(320.0).show(ui, |ui| match &plan {
+                Some(plan) => draw_review(ui, plan),
+                None => draw_mapping(ui, draft),
+            });
+
+            if let Some(error) = &draft.error {
+                ui.label(RichText::new(error).size(14.0).color(ButtonStyle::Danger.color()));
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| match &plan {
+                None => {
+                    let has_game = draft.options.mapping.contains(&ImportField::Game);
+                    if action_button(ui, "Next ⏵", Vec2::new(80.0, 25.0), Some(ButtonStyle::Success)).clicked() {
+                        if has_game {
+                            draft.reviewing = true;
+                            draft.error = None;
+                        } else {
+                            draft.error = Some("Map a column to the game name first".to_string());
+                        }
+                    }
+                }
+                Some(plan) => {
+                    if action_button(ui, "⏴ Back", Vec2::new(80.0, 25.0), None).clicked() {
+                        draft.reviewing = false;
+                    }
+                    let count = plan.importable().count();
+                    if action_button(
+                        ui,
+                        &format!("Import {} sessions", count),
+                        Vec2::new(150.0, 25.0),
+                        Some(ButtonStyle::Success),
+                    )
+                    .clicked()
+                        && count > 0
+                    {
+                        match apply_import(plan) {
+                            Ok(summary) => {
+                                draft.finished = Some(format!(
+                                    "Imported {} sessions and created {} games",
+                                    summary.sessions, summary.games
+                                ));
+                                draft.error = None;
+                                imported = true;
+                            }
+                            Err(err) => draft.error = Some(err),
+                        }
+                    }
+                }
+            });
+        },
+    );
+
+    if imported {
+        app.state.games = load_games();
+        app.missing_games = find_missing_games(&app.state.games);
+        app.goal_header = None;
+    }
+    if !window_open || close {
+        app.state.csv_import_draft = None;
+    }
+}
@@ -0,0 +1,177 @@
+use crate::core::json_storage::{Game, Session, load_all_sessions, load_categories, load_games};
+use crate::core::library_merge::{
+    ForeignLibrary, GameMerge, MergeTarget, apply_library_merge, load_foreign_library, plan_library_merge, retarget,
+    session_counts,
+};
+use crate::core::relink::find_missing_games;
+use crate::ui::app::{LibraryMergeDraft, TimeTrackerApp};
+use crate::ui::ui_patterns::{ButtonStyle, action_button};
+use eframe::egui::{self, ComboBox, RichText, ScrollArea, Ui, Vec2};
+use rfd::FileDialog;
+
+/// Asks for another library (backup archive or `games.json`) and opens its review.
+pub fn open_library_merge(app: &mut TimeTrackerApp) {
+    let Some(path) = FileDialog::new().add_filter("Library", &["zip", "json"]).pick_file() else {
+        return;
+    };
+
+    let draft = match load_foreign_library(&path) {
+        Ok(library) => {
+            let plan = plan_library_merge(&app.state.games, &library);
+            LibraryMergeDraft { library, plan, error: None, finished: None }
+        }
+        Err(err) => LibraryMergeDraft {
+            library: ForeignLibrary { source: path.display().to_string(), ..Default::default() },
+            plan: Vec::new(),
+            error: Some(err),
+            finished: None,
+        },
+    };
+    app.library_merge = Some(draft);
+}
+
+fn target_label(target: MergeTarget, games: &[Game]) -> String {
+    match target {
+        MergeTarget::New => "➕ Add as new game".to_string(),
+        MergeTarget::Skip => "✖ Skip".to_string(),
+        MergeTarget::Existing(id) => {
+            games.iter().find(|g| g.id == id).map_or("Unknown game".to_string(), |g| g.name.clone())
+        }
+    }
+}
+
+/// One foreign game with its target and conflicting details.
+fn draw_game_merge(ui: &mut Ui, merge: &mut GameMerge, games: &[Game], sessions: &[Session], library: &ForeignLibrary) {
+    let Some(foreign) = library.games.iter().find(|g| g.id == merge.foreign_id) else {
+        return;
+    };
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(&foreign.name).size(15.0).strong());
+        if let Some(reason) = merge.matched_by {
+            ui.label(RichText::new(format!("({})", reason.label())).size(13.0).weak());
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Into:").size(14.0));
+        let mut target = merge.target;
+        ComboBox::from_id_salt(("library_merge_target", merge.foreign_id))
+            .selected_text(target_label(target, games))
+            .width(220.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut target, MergeTarget::New, target_label(MergeTarget::New, games));
+                ui.selectable_value(&mut target, MergeTarget::Skip, target_label(MergeTarget::Skip, games));
+                for game in games {
+                    ui.selectable_value(&mut target, MergeTarget::Existing(game.id), &game.name);
+                }
+            });
+        if target != merge.target {
+            retarget(merge, target, games, library);
+        }
+
+        let (new, duplicates) = session_counts(merge, sessions, library);
+        ui.label(RichText::new(format!("+{} sessions, {} already here", new, duplicates)).size(13.0).weak());
+    });
+
+    if let MergeTarget::Existing(id) = merge.target
+        && let Some(local) = games.iter().find(|g| g.id == id)
+    {
+        for conflict in &mut merge.conflicts {
+            ui.horizontal(|ui| {
+                ui.add_space(16.0);
+                ui.label(RichText::new(format!("{}:", conflict.field.label())).size(14.0));
+                ui.radio_value(&mut conflict.use_foreign, false, format!("keep \"{}\"", conflict.field.value(local)));
+                ui.radio_value(&mut conflict.use_foreign, true, format!("use \"{}\"", conflict.field.value(foreign)));
+            });
+        }
+    }
+    ui.separator();
+}
+
+/// Renders the review of merging another machine's library into this one.
+pub fn draw_library_merge_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    let Some(draft) = app.library_merge.as_mut() else {
+        return;
+    };
+
+    let games = &app.state.games;
+    let mut window_open = true;
+    let mut merge_clicked = false;
+    let mut close_clicked = false;
+
+    egui::Window::new("Merge library")
+        .resizable(true)
+        .default_size(Vec2::new(560.0, 440.0))
+        .open(&mut window_open)
+        .show(ctx, |ui| {
+            ui.label(RichText::new(&draft.library.source).size(13.0).weak());
+
+            if let Some(message) = &draft.finished {
+                ui.label(RichText::new(message).size(16.0));
+                close_clicked = action_button(ui, "Close", Vec2::new(80.0, 25.0), None).clicked();
+                return;
+            }
+
+            let sessions = load_all_sessions();
+            let (new, duplicates) = draft
+                .plan
+                .iter()
+                .map(|m| session_counts(m, &sessions, &draft.library))
+                .fold((0, 0), |(a, b), (n, d)| (a + n, b + d));
+            let conflicts: usize = draft
+                .plan
+                .iter()
+                .filter(|m| matches!(m.target, MergeTarget::Existing(_)))
+                .map(|m| m.conflicts.len())
+                .sum();
+            ui.label(
+                RichText::new(format!(
+                    "{} games, {} sessions to add, {} already here, {} conflicting details",
+                    draft.library.games.len(),
+                    new,
+                    duplicates,
+                    conflicts
+                ))
+                .size(15.0),
+            );
+            ui.separator();
+
+            ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                for merge in &mut draft.plan {
+                    draw_game_merge(ui, merge, games, &sessions, &draft.library);
+                }
+            });
+
+            if let Some(error) = &draft.error {
+                ui.label(RichText::new(error).size(14.0).color(ButtonStyle::Danger.color()));
+            }
+
+            ui.horizontal(|ui| {
+                merge_clicked = !draft.plan.is_empty()
+                    && action_button(ui, "Merge", Vec2::new(80.0, 25.0), Some(ButtonStyle::Success)).clicked();
+                close_clicked = action_button(ui, "Cancel", Vec2::new(80.0, 25.0), None).clicked();
+            });
+        });
+
+    if merge_clicked {
+        match apply_library_merge(&draft.library, &draft.plan) {
+            Ok(summary) => {
+                draft.finished = Some(format!(
+                    "Added {} games, merged {} and added {} sessions ({} already here)",
+                    summary.games_added, summary.games_merged, summary.sessions_added, summary.sessions_skipped
+                ));
+                draft.error = None;
+                app.state.games = load_games();
+                app.state.categories = load_categories();
+                app.missing_games = find_missing_games(&app.state.games);
+                app.cover_textures.clear();
+                app.goal_header = None;
+            }
+            Err(err) => draft.error = Some(err),
+        }
+    }
+    if !window_open || close_clicked {
+        app.library_merge = None;
+    }
+}
@@ -0,0 +1,121 @@
+use crate::core::json_storage::{Game, LimitKind, PlayLimit, load_limits};
+use crate::core::reminders::{add_limit, delete_limit, update_limit};
+use crate::ui::app::TimeTrackerApp;
+use crate::ui::ui_patterns::{ButtonStyle, action_button};
+use eframe::egui::{self, Align2, ComboBox, DragValue, RichText, ScrollArea, Ui, Vec2};
+
+/// Draws the editable game, kind and interval of a reminder. Returns `true` if anything changed.
+fn draw_limit_fields(ui: &mut Ui, limit: &mut PlayLimit, games: &[Game]) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Remind").size(15.0));
+
+        ComboBox::from_id_salt(("limit_kind", limit.id)).selected_text(limit.kind.label()).show_ui(ui, |ui| {
+            for kind in LimitKind::ALL {
+                changed |= ui.selectable_value(&mut limit.kind, kind, kind.label()).changed();
+            }
+        });
+
+        changed |= ui.add(DragValue::new(&mut limit.minutes).range(1..=10_000).suffix(" min")).changed();
+
+        let selected = limit.game_id.and_then(|id| games.iter().find(|g| g.id == id)).map_or("Any game", |g| &g.name);
+        ComboBox::from_id_salt(("limit_game", limit.id)).selected_text(selected).show_ui(ui, |ui| {
+            changed |= ui.selectable_value(&mut limit.game_id, None, "Any game").changed();
+            for game in games {
+                changed |= ui.selectable_value(&mut limit.game_id, Some(game.id), &game.name).changed();
+            }
+        });
+    });
+
+    changed
+}
+
+/// Renders the window for session time limits and break reminders.
+pub fn draw_limits_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_limits_window {
+        return;
+    }
+
+    let mut window_open = true;
+    let mut changed = None;
+    let mut deleted = None;
+    let mut added = false;
+
+    egui::Window::new("Reminders").resizable(true).default_size(Vec2::new(420.0, 260.0)).open(&mut window_open).show(
+        ctx,
+        |ui| {
+            if action_button(ui, "➕ Add reminder", Vec2::new(130.0, 24.0), Some(ButtonStyle::Success)).clicked() {
+                added = true;
+            }
+            ui.label(RichText::new("Changes apply to games started afterwards").size(13.0).weak());
+
+            ui.separator();
+
+            ScrollArea::vertical().show(ui, |ui| {
+                if app.state.limits.is_empty() {
+                    ui.label(RichText::new("No reminders yet").size(16.0));
+                }
+
+                for limit in &mut app.state.limits {
+                    ui.horizontal(|ui| {
+                        if draw_limit_fields(ui, limit, &app.state.games) {
+                            changed = Some(limit.clone());
+                        }
+                        if action_button(ui, "🗑", Vec2::new(24.0, 20.0), Some(ButtonStyle::Danger))
+                            .on_hover_text("Delete reminder")
+                            .clicked()
+                        {
+                            deleted = Some(limit.id);
+                        }
+                    });
+                }
+            });
+        },
+    );
+
+    if added {
+        add_limit(None, LimitKind::Every, 45);
+    }
+    if let Some(limit) = &changed {
+        update_limit(limit);
+    }
+    if let Some(limit_id) = deleted {
+        delete_limit(limit_id);
+    }
+    if added || changed.is_some() || deleted.is_some() {
+        app.state.limits = load_limits();
+    }
+
+    if !window_open {
+        app.state.show_limits_window = false;
+    }
+}
+
+/// Shows fired reminders in the corner until they are dismissed.
+pub fn draw_reminders(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if app.reminders.is_empty() {
+        return;
+    }
+
+    let mut dismissed = None;
+
+    egui::Window::new("⏰ Reminder")
+        .anchor(Align2::RIGHT_TOP, Vec2::new(-10.0, 10.0))
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            for (i, (_, message)) in app.reminders.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(message).size(16.0));
+                    if action_button(ui, "OK", Vec2::new(40.0, 22.0), Some(ButtonStyle::Success)).clicked() {
+                        dismissed = Some(i);
+                    }
+                });
+            }
+        });
+
+    if let Some(i) = dismissed {
+        app.reminders.remove(i);
+    }
+}
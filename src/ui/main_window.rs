@@ -1,23 +1,86 @@
-use crate::core::game_crud::rename_game;
-use crate::ui::app::GameUpdate;
+use crate::core::category_crud::assign_category;
+use crate::core::duration::{format_duration, parse_duration};
+use crate::core::game_crud::{move_game, move_game_step, rename_game, set_game_status};
+use crate::core::game_filter::{GameFilter, SortColumn, visible_game_indices};
+use crate::core::goals::{goal_progress, goal_streak};
+use crate::core::json_storage::{Category, CategoryId, Game, GameId, PlayStatus, load_all_sessions, load_games};
+use crate::core::tag_crud::all_tags;
+use crate::ui::app::{GameUpdate, GoalHeader, MergeGamesDraft, RelinkDraft, SessionNoteDraft};
+use crate::ui::covers::{cover_texture, draw_cover};
+use crate::ui::goals_window::describe_goal;
+use crate::ui::import_window::open_csv_import;
+use crate::ui::library_merge_window::open_library_merge;
+use crate::ui::relink_window::locate_game;
 use crate::{core::game_launch::start_game, ui::app::TimeTrackerApp, ui::ui_patterns::*};
-use eframe::egui::{Align, Color32, Context, FontId, Key, Label, Layout, RichText, ScrollArea, Sense, TextEdit, Ui, Vec2, Visuals};
+use chrono::Local;
+use eframe::egui::{
+    Align, Color32, ComboBox, Context, CursorIcon, FontId, Frame, Id, Key, Label, Layout, ProgressBar, Response,
+    RichText, ScrollArea, Sense, Stroke, TextEdit, TextureHandle, Ui, UserAttentionType, Vec2, ViewportCommand,
+    Visuals,
+};
 use egui_extras::{Column, TableBuilder, TableRow};
 use std::sync::mpsc::Sender;
 
+/// Action requested from a game row
+enum RowAction {
+    Locate,
+    Sessions,
+    Delete,
+    SetCategory(Option<CategoryId>),
+    Select,
+    MoveUp,
+    MoveDown,
+    Details,
+    ToggleTagFilter(String),
+    SetStatus(PlayStatus),
+}
+
+/// Row data that isn't stored in the game itself
+struct RowContext<'a> {
+    missing: bool,
+    draggable: bool,
+    categories: &'a [Category],
+    tag_filter: &'a [String],
+    cover: Option<&'a TextureHandle>,
+}
+
+/// Draw a line above a row to show where a dragged game will land.
+fn draw_drop_marker(row_response: &Response) {
+    let painter = row_response.ctx.layer_painter(row_response.layer_id);
+    let stroke = Stroke::new(2.0, ButtonStyle::Success.color());
+    painter.hline(row_response.rect.x_range(), row_response.rect.top(), stroke);
+}
+
 /// Draw single row for one game
 fn draw_game_row(
     row: &mut TableRow,
-    game: &mut crate::core::json_storage::Game,
-    editing_name: &mut Option<u32>,
+    game: &mut Game,
+    editing_name: &mut Option<GameId>,
+    context: &RowContext,
     updates: &Sender<GameUpdate>,
-    on_sessions: impl FnOnce(u32),
-    on_delete: impl FnOnce(u32),
-) {
-    // Start button
+) -> Option<RowAction> {
+    let RowContext { missing, draggable, categories, tag_filter, cover } = *context;
+    let mut action = None;
+
+    // Drag handle
     row.col(|ui| {
-        if centered_button(ui, "▶", Vec2::new(80.0, 25.0), Some(ButtonStyle::Success)) {
-            start_game(game, updates.clone());
+        if draggable {
+            ui.dnd_drag_source(Id::new(("drag_game", game.id)), game.id, |ui| {
+                ui.label(RichText::new("☰").size(18.0));
+            })
+            .response
+            .on_hover_cursor(CursorIcon::Grab);
+        }
+    });
+
+    // Start button, or locate button if the executable is gone
+    row.col(|ui| {
+        if missing {
+            if centered_button(ui, "📂", Vec2::new(80.0, 25.0), Some(ButtonStyle::Danger)) {
+                action = Some(RowAction::Locate);
+            }
+        } else if centered_button(ui, "▶", Vec2::new(80.0, 25.0), Some(ButtonStyle::Success)) {
+            start_game(game, updates.clone(), ui.ctx().clone());
         }
     });
 
@@ -29,7 +92,7 @@ fn draw_game_row(
                     .font(FontId::proportional(20.0))
                     .desired_width(f32::INFINITY)
                     .background_color(Color32::from_rgb(200, 200, 200))
-                    .text_color(Color32::from_rgb(40, 40, 40))
+                    .text_color(Color32::from_rgb(40, 40, 40)),
             );
 
             let commit = response.lost_focus()
@@ -41,10 +104,72 @@ fn draw_game_row(
                 rename_game(game.id, game.name.clone());
             }
         } else {
-            let label = Label::new(RichText::new(&game.name).size(20.0)).sense(Sense::click());
-            if ui.add(label).double_clicked() {
+            if cover.is_some() && draw_cover(ui, cover, Vec2::splat(24.0)).clicked() {
+                action = Some(RowAction::Details);
+            }
+            ui.label(RichText::new(game.status.icon()).size(16.0)).on_hover_text(game.status.label());
+
+            let response = if missing {
+                let text = RichText::new(format!("⚠ {}", game.name)).size(20.0).color(ButtonStyle::Danger.color());
+                ui.add(Label::new(text).sense(Sense::click()))
+                    .on_hover_text(format!("Executable not found:\n{}", game.path))
+            } else {
+                ui.add(Label::new(RichText::new(&game.name).size(20.0)).sense(Sense::click()))
+            };
+
+            if response.double_clicked() {
                 *editing_name = Some(game.id);
+            } else if response.clicked() {
+                action = Some(RowAction::Select);
+            }
+
+            // Tag chips, click to filter by the tag
+            for tag in &game.tags {
+                if tag_chip(ui, tag, tag_filter.contains(tag)).clicked() {
+                    action = Some(RowAction::ToggleTagFilter(tag.clone()));
+                }
             }
+
+            // Right click: details, reorder or move to category
+            response.context_menu(|ui| {
+                if ui.button("ℹ Details…").clicked() {
+                    action = Some(RowAction::Details);
+                    ui.close();
+                }
+                ui.menu_button("Status", |ui| {
+                    for status in PlayStatus::ALL {
+                        let text = format!("{} {}", status.icon(), status.label());
+                        if ui.radio(game.status == status, text).clicked() {
+                            action = Some(RowAction::SetStatus(status));
+                            ui.close();
+                        }
+                    }
+                });
+                ui.separator();
+                if draggable {
+                    if ui.button("⏶ Move up (Alt+↑)").clicked() {
+                        action = Some(RowAction::MoveUp);
+                        ui.close();
+                    }
+                    if ui.button("⏷ Move down (Alt+↓)").clicked() {
+                        action = Some(RowAction::MoveDown);
+                        ui.close();
+                    }
+                    ui.separator();
+                }
+
+                ui.label(RichText::new("Move to category").strong());
+                for category in categories {
+                    if ui.radio(game.category_id == Some(category.id), &category.name).clicked() {
+                        action = Some(RowAction::SetCategory(Some(category.id)));
+                        ui.close();
+                    }
+                }
+                if ui.radio(game.category_id.is_none(), "Uncategorized").clicked() {
+                    action = Some(RowAction::SetCategory(None));
+                    ui.close();
+                }
+            });
         }
     });
 
@@ -53,27 +178,247 @@ fn draw_game_row(
         ui.label(RichText::new(&game.hours).size(20.0));
     });
 
+    // Last played date
+    row.col(|ui| {
+        let date = game.last_played.split_whitespace().next().unwrap_or("—");
+        ui.label(RichText::new(date).size(18.0)).on_hover_text(&game.last_played);
+    });
+
     // Sessions button
     row.col(|ui| {
         if centered_button(ui, "Check", Vec2::new(70.0, 25.0), Some(ButtonStyle::Neutral)) {
-            on_sessions(game.id);
+            action = Some(RowAction::Sessions);
         }
     });
 
     // Delete button
     row.col(|ui| {
         if centered_button(ui, "Delete", Vec2::new(70.0, 25.0), Some(ButtonStyle::Danger)) {
-            on_delete(game.id);
+            action = Some(RowAction::Delete);
+        }
+    });
+
+    action
+}
+
+/// Draw one card of the cover view: cover, name, playtime and Start button.
+fn draw_cover_card(
+    ui: &mut Ui,
+    game: &mut Game,
+    context: &RowContext,
+    selected: bool,
+    updates: &Sender<GameUpdate>,
+) -> Option<RowAction> {
+    let mut action = None;
+    let fill = if selected { ui.visuals().selection.bg_fill.gamma_multiply(0.4) } else { Color32::TRANSPARENT };
+
+    Frame::group(ui.style()).fill(fill).show(ui, |ui| {
+        ui.set_width(140.0);
+        ui.vertical_centered(|ui| {
+            let response = draw_cover(ui, context.cover, Vec2::splat(140.0)).on_hover_text("Double click for details");
+            if response.double_clicked() {
+                action = Some(RowAction::Details);
+            } else if response.clicked() {
+                action = Some(RowAction::Select);
+            }
+
+            let name = format!("{} {}", game.status.icon(), game.name);
+            let color = if context.missing { ButtonStyle::Danger.color() } else { ui.visuals().text_color() };
+            ui.add(Label::new(RichText::new(name).size(16.0).color(color)).truncate()).on_hover_text(&game.name);
+            ui.label(RichText::new(&game.hours).size(14.0));
+
+            if context.missing {
+                if action_button(ui, "📂 Locate", Vec2::new(100.0, 24.0), Some(ButtonStyle::Danger)).clicked() {
+                    action = Some(RowAction::Locate);
+                }
+            } else if action_button(ui, "▶ Start", Vec2::new(100.0, 24.0), Some(ButtonStyle::Success)).clicked() {
+                start_game(game, updates.clone(), ui.ctx().clone());
+            }
+        });
+    });
+
+    action
+}
+
+/// Draw collapsible header row of a category with its total playtime.
+/// Returns `true` if the header was clicked.
+fn draw_category_row(row: &mut TableRow, name: &str, games_count: usize, total_secs: u64, collapsed: bool) -> bool {
+    let mut clicked = false;
+
+    row.col(|_| {});
+    row.col(|ui| {
+        if centered_button(ui, if collapsed { "⏵" } else { "⏷" }, Vec2::new(30.0, 25.0), None) {
+            clicked = true;
         }
     });
+    row.col(|ui| {
+        let text = RichText::new(format!("{} ({})", name, games_count)).size(20.0).strong();
+        if ui.add(Label::new(text).sense(Sense::click())).clicked() {
+            clicked = true;
+        }
+    });
+    row.col(|ui| {
+        ui.label(RichText::new(format_duration(total_secs)).size(20.0).strong());
+    });
+
+    clicked
+}
+
+/// Cycles sorting by a column: ascending, descending, then back to the saved order.
+fn toggle_sort(app: &mut TimeTrackerApp, column: SortColumn) {
+    if app.state.sort_column != Some(column) {
+        app.state.sort_column = Some(column);
+        app.state.sort_descending = false;
+    } else if !app.state.sort_descending {
+        app.state.sort_descending = true;
+    } else {
+        app.state.sort_column = None;
+        app.state.sort_descending = false;
+    }
+}
+
+/// Draws the search box and sort selector above the table.
+fn draw_search_bar(app: &mut TimeTrackerApp, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        labeled_text_edit(ui, "🔍", &mut app.state.games_search, 18.0, Vec2::new(220.0, 24.0), 16.0);
+        if !app.state.games_search.is_empty() && action_button(ui, "✖", Vec2::new(24.0, 24.0), None).clicked() {
+            app.state.games_search.clear();
+        }
+
+        ui.add_space(20.0);
+        ui.label(RichText::new("Sort by:").size(18.0));
+
+        let selected = app.state.sort_column.map_or("Manual order", |c| c.label());
+        ComboBox::from_id_salt("games_sort").selected_text(selected).show_ui(ui, |ui| {
+            ui.selectable_value(&mut app.state.sort_column, None, "Manual order");
+            for column in SortColumn::ALL {
+                ui.selectable_value(&mut app.state.sort_column, Some(column), column.label());
+            }
+        });
+
+        if app.state.sort_column.is_some() {
+            let arrow = if app.state.sort_descending { "⏷" } else { "⏶" };
+            if action_button(ui, arrow, Vec2::new(24.0, 24.0), None).clicked() {
+                app.state.sort_descending = !app.state.sort_descending;
+            }
+        }
+
+        ui.add_space(20.0);
+        ui.label(RichText::new("Status:").size(18.0));
+
+        let selected = app.state.status_filter.map_or("All".to_string(), |s| format!("{} {}", s.icon(), s.label()));
+        ComboBox::from_id_salt("games_status_filter").selected_text(selected).show_ui(ui, |ui| {
+            ui.selectable_value(&mut app.state.status_filter, None, "All");
+            for status in PlayStatus::ALL {
+                ui.selectable_value(
+                    &mut app.state.status_filter,
+                    Some(status),
+                    format!("{} {}", status.icon(), status.label()),
+                );
+            }
+        });
+    });
+
+    // Tag filter bar
+    let tags = all_tags(&app.state.games);
+    if tags.is_empty() {
+        return;
+    }
+
+    let mut toggled = None;
+    ui.horizontal_wrapped(|ui| {
+        ui.label(RichText::new("Tags:").size(18.0));
+
+        let mode = if app.state.tag_filter_match_all { "AND" } else { "OR" };
+        if action_button(ui, mode, Vec2::new(40.0, 20.0), None)
+            .on_hover_text("AND: games with all selected tags\nOR: games with any selected tag")
+            .clicked()
+        {
+            app.state.tag_filter_match_all = !app.state.tag_filter_match_all;
+        }
+
+        for (tag, _) in &tags {
+            if tag_chip(ui, tag, app.state.tag_filter.contains(tag)).clicked() {
+                toggled = Some(tag.clone());
+            }
+        }
+    });
+
+    if let Some(tag) = toggled {
+        toggle_tag_filter(app, tag);
+    }
+}
+
+/// Adds the tag to the table filter, or removes it if already there.
+fn toggle_tag_filter(app: &mut TimeTrackerApp, tag: String) {
+    if app.state.tag_filter.contains(&tag) {
+        app.state.tag_filter.retain(|t| *t != tag);
+    } else {
+        app.state.tag_filter.push(tag);
+    }
+}
+
+/// Draws the progress of the headline goal (the first library-wide one) next to "Add game".
+fn draw_goal_header(app: &mut TimeTrackerApp, ui: &mut Ui) {
+    let Some(goal) = app.state.goals.iter().find(|g| g.game_id.is_none()).or(app.state.goals.first()) else {
+        return;
+    };
+
+    let today = Local::now().date_naive();
+    if app.goal_header.as_ref().is_none_or(|header| header.day != today) {
+        let sessions = load_all_sessions();
+        let (played, streak) = (goal_progress(goal, &sessions, today), goal_streak(goal, &sessions, today));
+        app.goal_header = Some(GoalHeader { day: today, played, streak });
+    }
+    let Some(GoalHeader { played, streak, .. }) = app.goal_header else { return };
+    let target = (goal.minutes * 60).max(1);
+
+    let met = if played >= target { "✔" } else { "🎯" };
+    let text = format!("{} {} / {} · 🔥 {}", met, format_duration(played), format_duration(target), streak.current);
+    let response = ui
+        .add(ProgressBar::new(played as f32 / target as f32).desired_width(230.0).text(text))
+        .interact(Sense::click())
+        .on_hover_text(format!(
+            "{}\nLongest streak: {}\nClick to edit goals",
+            describe_goal(goal, &app.state.games),
+            streak.longest
+        ));
+    if response.clicked() {
+        app.state.show_goals_window = true;
+    }
 }
 
 /// Draws the main games table with actions: Start, Sessions, Delete.
 pub fn draw_games_table(app: &mut TimeTrackerApp, ui: &mut Ui, ctx: &Context) {
     // Get updates about running games
     while let Ok(update) = app.updates_rx.try_recv() {
-        if let Some(game) = app.state.games.iter_mut().find(|g| g.id == update.game_id) {
-            game.hours = update.hours;
+        match update {
+            GameUpdate::Started { game_id, change } => {
+                if let Some(game) = app.state.games.iter_mut().find(|g| g.id == game_id) {
+                    game.status = change.status;
+                    game.status_history.push(change);
+                }
+            }
+            GameUpdate::Finished { game_id, hours, session_id, session_date } => {
+                if let Some(game) = app.state.games.iter_mut().find(|g| g.id == game_id) {
+                    game.hours = hours;
+                    game.last_played = session_date.clone();
+                }
+                app.goal_header = None;
+
+                // Ask where the player stopped
+                app.state.session_note_draft = Some(SessionNoteDraft {
+                    game_id,
+                    session_id,
+                    date: session_date,
+                    note: String::new(),
+                    progress: String::new(),
+                });
+            }
+            GameUpdate::Reminder { game_id, message } => {
+                app.reminders.push((game_id, message));
+                ctx.send_viewport_cmd(ViewportCommand::RequestUserAttention(UserAttentionType::Informational));
+            }
         }
     }
     // "Add game" button
@@ -81,6 +426,67 @@ pub fn draw_games_table(app: &mut TimeTrackerApp, ui: &mut Ui, ctx: &Context) {
         if action_button(ui, "➕ Add game", Vec2::new(150.0, 30.0), Some(ButtonStyle::Success)).clicked() {
             app.state.show_add_game_window = true;
         }
+        draw_goal_header(app, ui);
+        ui.menu_button(RichText::new("☰ Library").size(16.0), |ui| {
+            if ui.button("📊 Statistics").clicked() {
+                app.state.show_statistics_window = true;
+            }
+            if ui.button("📅 Calendar").clicked() {
+                app.state.show_heatmap_window = true;
+            }
+            if ui.button("🎉 Year in review").clicked() {
+                app.state.show_year_review_window = true;
+            }
+            if ui.button("🎯 Goals").clicked() {
+                app.state.show_goals_window = true;
+            }
+            if ui.button("⏰ Reminders").clicked() {
+                app.state.show_limits_window = true;
+            }
+            if ui.button("🗂 Categories").clicked() {
+                app.state.show_categories_window = true;
+            }
+            if ui.button("🏷 Tags").clicked() {
+                app.state.show_tags_window = true;
+            }
+            if ui.button("📥 Import CSV…").clicked() {
+                open_csv_import(app);
+            }
+            if ui.button("📤 Export CSV").clicked() {
+                app.state.show_export_window = true;
+            }
+            if ui.button("🗄 Backup & restore").clicked() {
+                app.state.show_backup_window = true;
+            }
+            if ui.button("🔀 Merge library…").on_hover_text("Combine the history of another machine").clicked() {
+                open_library_merge(app);
+            }
+            if ui.button("🔄 Folder sync").clicked() {
+                app.state.show_sync_window = true;
+            }
+            if ui.button("🔗 Merge games").clicked() {
+                app.state.merge_games_draft = Some(MergeGamesDraft::default());
+            }
+            if ui
+                .button("⚠ Overlapping sessions")
+                .on_hover_text("Find sessions that count the same time twice")
+                .clicked()
+            {
+                app.state.show_overlaps_window = true;
+            }
+        });
+        if !app.missing_games.is_empty()
+            && action_button(ui, "📁 Relink", Vec2::new(90.0, 30.0), Some(ButtonStyle::Danger))
+                .on_hover_text(format!("{} game(s) not found, relink a moved library folder", app.missing_games.len()))
+                .clicked()
+        {
+            app.state.relink_draft = Some(RelinkDraft::default());
+        }
+        let (view_icon, view_hint) =
+            if app.state.cover_view { ("☰", "Show as table") } else { ("🖼", "Show as cover grid") };
+        if action_button(ui, view_icon, Vec2::new(30.0, 30.0), None).on_hover_text(view_hint).clicked() {
+            app.state.cover_view = !app.state.cover_view;
+        }
         if action_button(ui, "🌓", Vec2::new(30.0, 30.0), None).clicked() {
             app.state.dark_mode = !app.state.dark_mode;
             if app.state.dark_mode {
@@ -92,8 +498,52 @@ pub fn draw_games_table(app: &mut TimeTrackerApp, ui: &mut Ui, ctx: &Context) {
     });
 
     ui.separator();
+
+    draw_search_bar(app, ui);
+
     ui.separator();
 
+    let mut row_action = None;
+    let mut toggled_group = None;
+    let mut dropped = None;
+    let mut clicked_header = None;
+
+    let filter = GameFilter {
+        query: &app.state.games_search,
+        tags: &app.state.tag_filter,
+        match_all_tags: app.state.tag_filter_match_all,
+        status: app.state.status_filter,
+    };
+    let visible = visible_game_indices(&app.state.games, &filter, app.state.sort_column, app.state.sort_descending);
+    let filtering = filter.is_active();
+    // Reordering only makes sense while the list shows the saved order, not sorted or ranked by the search
+    let manual_order = app.state.sort_column.is_none() && app.state.games_search.trim().is_empty();
+    let sort_arrow = |column: SortColumn| match app.state.sort_column {
+        Some(sorted) if sorted == column => {
+            if app.state.sort_descending {
+                " ⏷"
+            } else {
+                " ⏶"
+            }
+        }
+        _ => "",
+    };
+
+    // Alt+Up / Alt+Down move the selected game
+    if let Some(game_id) = app.state.selected_game
+        && app.state.editing_name.is_none()
+        && manual_order
+    {
+        let (up, down) = ctx.input(|i| {
+            (i.modifiers.alt && i.key_pressed(Key::ArrowUp), i.modifiers.alt && i.key_pressed(Key::ArrowDown))
+        });
+        if up {
+            row_action = Some((game_id, RowAction::MoveUp));
+        } else if down {
+            row_action = Some((game_id, RowAction::MoveDown));
+        }
+    }
+
     ScrollArea::vertical().show(ui, |ui| {
         ui.set_min_width(ui.available_width());
 
@@ -102,23 +552,55 @@ pub fn draw_games_table(app: &mut TimeTrackerApp, ui: &mut Ui, ctx: &Context) {
             return;
         }
 
+        if app.state.cover_view {
+            ui.horizontal_wrapped(|ui| {
+                for &i in &visible {
+                    let game = &mut app.state.games[i];
+                    let cover = cover_texture(&mut app.cover_textures, ctx, &game.cover);
+                    let context = RowContext {
+                        missing: app.missing_games.contains(&game.id),
+                        draggable: false,
+                        categories: &app.state.categories,
+                        tag_filter: &app.state.tag_filter,
+                        cover: cover.as_ref(),
+                    };
+                    let selected = app.state.selected_game == Some(game.id);
+                    if let Some(action) = draw_cover_card(ui, game, &context, selected, &app.updates_tx) {
+                        row_action = Some((game.id, action));
+                    }
+                }
+            });
+            return;
+        }
+
         TableBuilder::new(ui)
             .striped(true)
             .cell_layout(Layout::left_to_right(Align::Center))
+            .column(Column::exact(24.0)) // Drag handle
             .column(Column::exact(80.0)) // Start button
             .column(Column::remainder()) // Game name
             .column(Column::exact(120.0)) // Played hours
+            .column(Column::exact(120.0)) // Last played
             .column(Column::exact(90.0)) // Sessions
             .column(Column::exact(80.0)) // Delete
             .column(Column::exact(10.0)) // Spacer
             .body(|mut body| {
                 // Header row
                 body.row(30.0, |mut row| {
+                    row.col(|_| {});
                     header_cell(&mut row, "Start");
                     row.col(|ui| {
-                        ui.label(RichText::new("Game name").size(20.0).strong());
+                        let text = format!("Game name{}", sort_arrow(SortColumn::Name));
+                        if ui.add(Label::new(RichText::new(text).size(20.0).strong()).sense(Sense::click())).clicked() {
+                            clicked_header = Some(SortColumn::Name);
+                        }
                     });
-                    header_cell(&mut row, "Played hours");
+                    if sortable_header_cell(&mut row, &format!("Played hours{}", sort_arrow(SortColumn::TotalTime))) {
+                        clicked_header = Some(SortColumn::TotalTime);
+                    }
+                    if sortable_header_cell(&mut row, &format!("Last played{}", sort_arrow(SortColumn::LastPlayed))) {
+                        clicked_header = Some(SortColumn::LastPlayed);
+                    }
                     header_cell(&mut row, "Sessions");
                     header_cell(&mut row, "Delete");
                     row.col(|ui| {
@@ -126,22 +608,125 @@ pub fn draw_games_table(app: &mut TimeTrackerApp, ui: &mut Ui, ctx: &Context) {
                     });
                 });
 
-                // Game rows
-                for game in &mut app.state.games {
-                    body.row(30.0, |mut row| {
-                        draw_game_row(
-                            &mut row,
-                            game,
-                            &mut app.state.editing_name,
-                            &app.updates_tx,
-                            |id| app.state.show_sessions_window = Some(id),
-                            |id| app.state.show_confirm_delete_window = Some(id),
-                        );
-                    });
+                // Without categories the list stays flat
+                let mut groups: Vec<(Option<CategoryId>, String)> =
+                    app.state.categories.iter().map(|c| (Some(c.id), c.name.clone())).collect();
+                let grouped = !groups.is_empty();
+                groups.push((None, "Uncategorized".to_string()));
+
+                for (group_id, group_name) in &groups {
+                    // Games pointing at a deleted category count as uncategorized
+                    let group_of =
+                        |game: &Game| game.category_id.filter(|id| app.state.categories.iter().any(|c| c.id == *id));
+                    let group_rows: Vec<usize> =
+                        visible.iter().copied().filter(|&i| group_of(&app.state.games[i]) == *group_id).collect();
+                    let collapsed = grouped && app.state.collapsed_categories.contains(group_id);
+
+                    if grouped {
+                        if group_rows.is_empty() && (group_id.is_none() || filtering) {
+                            continue;
+                        }
+                        let total = group_rows.iter().map(|&i| parse_duration(&app.state.games[i].hours)).sum();
+
+                        body.row(30.0, |mut row| {
+                            if draw_category_row(&mut row, group_name, group_rows.len(), total, collapsed) {
+                                toggled_group = Some(*group_id);
+                            }
+
+                            // Dropping on a header moves the game to the end of the category
+                            let response = row.response();
+                            if let Some(dragged) = response.dnd_release_payload::<GameId>() {
+                                dropped = Some((*dragged, None, *group_id));
+                            } else if response.dnd_hover_payload::<GameId>().is_some() {
+                                draw_drop_marker(&response);
+                            }
+                        });
+                    }
+
+                    if collapsed {
+                        continue;
+                    }
+
+                    // Game rows
+                    for i in group_rows {
+                        let game = &mut app.state.games[i];
+                        let cover = cover_texture(&mut app.cover_textures, ctx, &game.cover);
+                        body.row(30.0, |mut row| {
+                            row.set_selected(app.state.selected_game == Some(game.id));
+                            let context = RowContext {
+                                missing: app.missing_games.contains(&game.id),
+                                draggable: manual_order,
+                                categories: &app.state.categories,
+                                tag_filter: &app.state.tag_filter,
+                                cover: cover.as_ref(),
+                            };
+                            if let Some(action) =
+                                draw_game_row(&mut row, game, &mut app.state.editing_name, &context, &app.updates_tx)
+                            {
+                                row_action = Some((game.id, action));
+                            }
+
+                            // Dropping on a row puts the game right above it
+                            let response = row.response();
+                            if let Some(dragged) = response.dnd_release_payload::<GameId>() {
+                                dropped = Some((*dragged, Some(game.id), *group_id));
+                            } else if response.dnd_hover_payload::<GameId>().is_some() {
+                                draw_drop_marker(&response);
+                            }
+                        });
+                    }
                 }
             });
     });
 
+    if let Some(column) = clicked_header {
+        toggle_sort(app, column);
+    }
+
+    if let Some(group_id) = toggled_group {
+        let collapsed = &mut app.state.collapsed_categories;
+        if let Some(pos) = collapsed.iter().position(|c| *c == group_id) {
+            collapsed.remove(pos);
+        } else {
+            collapsed.push(group_id);
+        }
+    }
+
+    let visible_ids: Vec<GameId> = visible.iter().filter_map(|&i| app.state.games.get(i)).map(|g| g.id).collect();
+    match row_action {
+        Some((game_id, RowAction::Locate)) => locate_game(app, game_id),
+        Some((game_id, RowAction::Sessions)) => app.state.show_sessions_window = Some(game_id),
+        Some((game_id, RowAction::Delete)) => app.state.show_confirm_delete_window = Some(game_id),
+        Some((game_id, RowAction::SetCategory(category_id))) => {
+            assign_category(game_id, category_id);
+            app.state.games = load_games();
+        }
+        Some((game_id, RowAction::Select)) => app.state.selected_game = Some(game_id),
+        Some((game_id, RowAction::Details)) => app.state.show_game_details = Some(game_id),
+        Some((game_id, RowAction::SetStatus(status))) => {
+            set_game_status(game_id, status);
+            app.state.games = load_games();
+        }
+        Some((_, RowAction::ToggleTagFilter(tag))) => toggle_tag_filter(app, tag),
+        Some((game_id, RowAction::MoveUp)) => {
+            move_game_step(game_id, true, &visible_ids);
+            app.state.games = load_games();
+        }
+        Some((game_id, RowAction::MoveDown)) => {
+            move_game_step(game_id, false, &visible_ids);
+            app.state.games = load_games();
+        }
+        None => {}
+    }
+
+    if let Some((game_id, before_id, category_id)) = dropped
+        && before_id != Some(game_id)
+    {
+        move_game(game_id, before_id, category_id);
+        app.state.selected_game = Some(game_id);
+        app.state.games = load_games();
+    }
+
     ui.separator();
     ui.separator();
 }
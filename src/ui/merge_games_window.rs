@@ -0,0 +1,88 @@
+use crate::core::json_storage::{Game, GameId};
+use crate::{core::game_crud::merge_games, ui::app::TimeTrackerApp, ui::ui_patterns};
+use eframe::egui::{ComboBox, Context, RichText, Ui, Vec2, Window};
+
+/// Draws a combo box for picking one game of the library.
+fn game_combo(ui: &mut Ui, id: &str, games: &[Game], selected: &mut Option<GameId>) {
+    let selected_name = selected.and_then(|id| games.iter().find(|g| g.id == id)).map_or("Select game", |g| &g.name);
+
+    ComboBox::from_id_salt(id).selected_text(selected_name).width(260.0).show_ui(ui, |ui| {
+        for game in games {
+            ui.selectable_value(selected, Some(game.id), &game.name);
+        }
+    });
+}
+
+/// Draws a choice between the donor and target value of a field.
+/// `keep_donor` is `true` when the donor value is chosen.
+fn value_choice(ui: &mut Ui, label: &str, donor: &str, target: &str, keep_donor: &mut bool) {
+    ui.label(RichText::new(label).size(16.0).strong());
+    ui.radio_value(keep_donor, false, target);
+    ui.radio_value(keep_donor, true, donor);
+}
+
+/// Renders the window for merging two library entries into one.
+pub fn draw_merge_games_window(app: &mut TimeTrackerApp, ctx: &Context) {
+    let Some(draft) = app.state.merge_games_draft.as_mut() else {
+        return;
+    };
+
+    let games = &app.state.games;
+    let mut merge_clicked = false;
+    let mut cancel_clicked = false;
+
+    Window::new("Merge games").collapsible(false).resizable(false).show(ctx, |ui| {
+        ui.label(RichText::new("Move all sessions from:").size(16.0));
+        game_combo(ui, "merge_donor", games, &mut draft.donor_id);
+        ui.label(RichText::new("into:").size(16.0));
+        game_combo(ui, "merge_target", games, &mut draft.target_id);
+
+        let donor = draft.donor_id.and_then(|id| games.iter().find(|g| g.id == id));
+        let target = draft.target_id.and_then(|id| games.iter().find(|g| g.id == id));
+
+        if let (Some(donor), Some(target)) = (donor, target) {
+            ui.separator();
+            value_choice(ui, "Keep name:", &donor.name, &target.name, &mut draft.keep_donor_name);
+            value_choice(ui, "Keep path:", &donor.path, &target.path, &mut draft.keep_donor_path);
+        }
+
+        if let Some(error) = &draft.error {
+            ui.label(RichText::new(error).size(14.0).color(ui_patterns::ButtonStyle::Danger.color()));
+        }
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            (merge_clicked, cancel_clicked) = ui_patterns::centered_two_buttons(
+                ui,
+                ("Merge", "Cancel"),
+                Vec2::new(80.0, 25.0),
+                20.0,
+                5.0,
+                (Some(ui_patterns::ButtonStyle::Danger), None),
+            );
+        });
+    });
+
+    if merge_clicked {
+        let donor = draft.donor_id.and_then(|id| games.iter().find(|g| g.id == id)).cloned();
+        let target = draft.target_id.and_then(|id| games.iter().find(|g| g.id == id)).cloned();
+
+        let result = match (donor, target) {
+            (Some(donor), Some(target)) => {
+                let name = if draft.keep_donor_name { donor.name } else { target.name };
+                let path = if draft.keep_donor_path { donor.path } else { target.path };
+                merge_games(donor.id, target.id, name, path, app)
+            }
+            _ => Err("Select both games".to_string()),
+        };
+
+        if let Err(error) = result
+            && let Some(draft) = app.state.merge_games_draft.as_mut()
+        {
+            draft.error = Some(error);
+        }
+    } else if cancel_clicked {
+        app.state.merge_games_draft = None;
+    }
+}
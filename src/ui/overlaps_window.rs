@@ -0,0 +1,98 @@
+use crate::core::duration::format_duration;
+use crate::core::json_storage::{Session, load_all_sessions, load_games};
+use crate::core::session_overlaps::{OverlapResolution, SessionOverlap, find_overlaps, resolve_overlap};
+use crate::ui::app::TimeTrackerApp;
+use crate::ui::ui_patterns::{ButtonStyle, action_button};
+use eframe::egui::{self, RichText, ScrollArea, Vec2};
+
+/// Describes one session of an overlap, e.g. `"Game: 2025-01-01 10:00:00 (1h 0m 0s)"`.
+fn describe_session(app: &TimeTrackerApp, session: &Session) -> String {
+    let name = app.state.games.iter().find(|g| g.id == session.game_id).map_or("Unknown game", |g| g.name.as_str());
+    format!("{}: ended {} ({})", name, session.date, session.duration)
+}
+
+/// Draws one overlap with resolution buttons.
+/// Returns the chosen resolution.
+fn draw_overlap(ui: &mut egui::Ui, app: &TimeTrackerApp, overlap: &SessionOverlap) -> Option<OverlapResolution> {
+    let mut choice = None;
+
+    ui.group(|ui| {
+        let kind = if overlap.same_game() { "Same game" } else { "Different games" };
+        ui.label(RichText::new(format!("{} — {} counted twice", kind, format_duration(overlap.overlap_secs))).strong());
+        ui.label(describe_session(app, &overlap.earlier));
+        ui.label(describe_session(app, &overlap.later));
+
+        ui.horizontal(|ui| {
+            let size = Vec2::new(90.0, 22.0);
+            if action_button(ui, "Trim later", size, Some(ButtonStyle::Success)).clicked() {
+                choice = Some(OverlapResolution::TrimLater);
+            }
+            if action_button(ui, "Drop first", size, Some(ButtonStyle::Danger)).clicked() {
+                choice = Some(OverlapResolution::DropEarlier);
+            }
+            if action_button(ui, "Drop second", size, Some(ButtonStyle::Danger)).clicked() {
+                choice = Some(OverlapResolution::DropLater);
+            }
+            if action_button(ui, "Keep", size, Some(ButtonStyle::Neutral)).clicked() {
+                choice = Some(OverlapResolution::Keep);
+            }
+        });
+    });
+
+    choice
+}
+
+/// Renders the review window listing overlapping sessions.
+pub fn draw_overlaps_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_overlaps_window {
+        return;
+    }
+
+    let mut window_open = true;
+    let mut changed = false;
+
+    egui::Window::new("Overlapping sessions")
+        .resizable(true)
+        .default_size(Vec2::new(460.0, 300.0))
+        .open(&mut window_open)
+        .show(ctx, |ui| {
+            let overlaps: Vec<SessionOverlap> = find_overlaps(&load_all_sessions())
+                .into_iter()
+                .filter(|o| !app.state.kept_overlaps.contains(&o.key()))
+                .collect();
+
+            if let Some(error) = &app.state.session_edit_error {
+                ui.label(RichText::new(error).size(14.0).color(ButtonStyle::Danger.color()));
+            }
+
+            ScrollArea::vertical().show(ui, |ui| {
+                ui.set_min_width(ui.available_width());
+
+                if overlaps.is_empty() {
+                    ui.label(RichText::new("No overlapping sessions").size(18.0));
+                }
+
+                for overlap in &overlaps {
+                    match draw_overlap(ui, app, overlap) {
+                        Some(OverlapResolution::Keep) => app.state.kept_overlaps.push(overlap.key()),
+                        Some(resolution) => match resolve_overlap(overlap, resolution) {
+                            Ok(()) => changed = true,
+                            Err(error) => app.state.session_edit_error = Some(error),
+                        },
+                        None => {}
+                    }
+                }
+            });
+        });
+
+    if changed {
+        app.state.session_edit_error = None;
+        app.state.games = load_games();
+        app.goal_header = None;
+    }
+
+    if !window_open {
+        app.state.show_overlaps_window = false;
+        app.state.session_edit_error = None;
+    }
+}
@@ -0,0 +1,84 @@
+use crate::core::json_storage::{GameId, load_games};
+use crate::core::relink::{count_prefix_matches, find_missing_games, moved_prefix, relink_game, relink_path_prefix};
+use crate::ui::app::{RelinkDraft, TimeTrackerApp};
+use crate::ui::ui_patterns;
+use eframe::egui::{Context, RichText, Vec2, Window};
+use rfd::FileDialog;
+use std::path::Path;
+
+/// Reloads games and re-checks which executables are missing.
+fn refresh_games(app: &mut TimeTrackerApp) {
+    app.state.games = load_games();
+    app.missing_games = find_missing_games(&app.state.games);
+}
+
+/// Asks the user for the new location of a game executable and updates its path.
+///
+/// If other games are still missing under the same old folder, the bulk relink
+/// window is opened prefilled with the detected folder change.
+pub fn locate_game(app: &mut TimeTrackerApp, game_id: GameId) {
+    let Some(old_path) = app.state.games.iter().find(|g| g.id == game_id).map(|g| g.path.clone()) else {
+        return;
+    };
+
+    let mut dialog = FileDialog::new();
+    if let Some(file_name) = Path::new(&old_path).file_name() {
+        dialog = dialog.set_file_name(file_name.to_string_lossy());
+    }
+    let Some(new_path) = dialog.pick_file() else {
+        return;
+    };
+    let new_path = new_path.to_string_lossy().to_string();
+
+    relink_game(game_id, new_path.clone());
+    refresh_games(app);
+
+    if let Some((old_prefix, new_prefix)) = moved_prefix(&old_path, &new_path) {
+        let still_missing: Vec<_> =
+            app.state.games.iter().filter(|g| app.missing_games.contains(&g.id)).cloned().collect();
+        if count_prefix_matches(&still_missing, &old_prefix) > 0 {
+            app.state.relink_draft = Some(RelinkDraft { old_prefix, new_prefix });
+        }
+    }
+}
+
+/// Renders the window for rewriting a moved library folder across all games.
+pub fn draw_relink_window(app: &mut TimeTrackerApp, ctx: &Context) {
+    let Some(draft) = app.state.relink_draft.as_mut() else {
+        return;
+    };
+
+    let matches = count_prefix_matches(&app.state.games, draft.old_prefix.trim());
+    let mut relink_clicked = false;
+    let mut cancel_clicked = false;
+
+    Window::new("Relink library folder").collapsible(false).resizable(false).show(ctx, |ui| {
+        ui.label(RichText::new("Replace the beginning of game paths:").size(16.0));
+
+        ui_patterns::labeled_text_edit(ui, "Old folder:", &mut draft.old_prefix, 16.0, Vec2::new(300.0, 24.0), 16.0);
+        ui_patterns::labeled_text_edit(ui, "New folder:", &mut draft.new_prefix, 16.0, Vec2::new(300.0, 24.0), 16.0);
+
+        ui.label(RichText::new(format!("{} game(s) will be relinked", matches)).size(14.0));
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            (relink_clicked, cancel_clicked) = ui_patterns::centered_two_buttons(
+                ui,
+                ("Relink", "Cancel"),
+                Vec2::new(80.0, 25.0),
+                20.0,
+                5.0,
+                (Some(ui_patterns::ButtonStyle::Success), None),
+            );
+        });
+    });
+
+    if relink_clicked {
+        relink_path_prefix(draft.old_prefix.trim(), draft.new_prefix.trim());
+        app.state.relink_draft = None;
+        refresh_games(app);
+    } else if cancel_clicked {
+        app.state.relink_draft = None;
+    }
+}
@@ -0,0 +1,167 @@
+use crate::core::duration::format_duration;
+use crate::core::json_storage::Session;
+use crate::core::statistics::{
+    DateRange, Period, cumulative_playtime, playtime_per_day, playtime_per_week, session_length_histogram,
+    sessions_in_range,
+};
+use crate::ui::app::SessionChartsState;
+use crate::ui::ui_patterns::{ButtonStyle, action_button, labeled_text_edit};
+use chrono::{Datelike, Days, Local, NaiveDate, NaiveDateTime, Timelike};
+use eframe::egui::{RichText, Ui, Vec2};
+use egui_plot::{Bar, BarChart, GridMark, Line, Plot, PlotPoints};
+use std::ops::RangeInclusive;
+
+/// Length of one histogram bucket.
+const HISTOGRAM_BUCKET_SECS: u64 = 15 * 60;
+const PLOT_HEIGHT: f32 = 120.0;
+
+/// Plot x coordinate of a moment: days since the common era, with the time as fraction.
+fn day_x(time: NaiveDateTime) -> f64 {
+    time.date().num_days_from_ce() as f64 + time.time().num_seconds_from_midnight() as f64 / 86_400.0
+}
+
+/// Axis label of a plot x coordinate made by [`day_x`].
+fn format_day_x(mark: GridMark, _range: &RangeInclusive<f64>) -> String {
+    NaiveDate::from_num_days_from_ce_opt(mark.value.floor() as i32)
+        .map(|d| d.format("%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Non-interactive plot with the given id.
+fn chart(id: &str) -> Plot<'static> {
+    Plot::new(id)
+        .height(PLOT_HEIGHT)
+        .allow_zoom(false)
+        .allow_drag(false)
+        .allow_scroll(false)
+        .allow_boxed_zoom(false)
+        .y_axis_formatter(|mark, _| format!("{}h", mark.value))
+}
+
+/// Resolves the entered range. Empty or invalid bounds fall back to the first session and today.
+fn selected_range(state: &SessionChartsState, sessions: &[Session]) -> (NaiveDate, NaiveDate) {
+    let today = Local::now().date_naive();
+    let first_played = sessions.iter().filter_map(|s| s.start_time()).min().map_or(today, |t| t.date());
+
+    let from = state.from.trim().parse().unwrap_or(first_played);
+    let to = state.to.trim().parse().unwrap_or(today);
+    (from, to.max(from))
+}
+
+/// Draws the date range inputs with shortcuts for common periods.
+fn draw_range_selector(ui: &mut Ui, state: &mut SessionChartsState) {
+    ui.horizontal(|ui| {
+        labeled_text_edit(ui, "From:", &mut state.from, 15.0, Vec2::new(95.0, 22.0), 15.0);
+        labeled_text_edit(ui, "To:", &mut state.to, 15.0, Vec2::new(95.0, 22.0), 15.0);
+    });
+
+    ui.horizontal(|ui| {
+        let today = Local::now().date_naive();
+        for period in [Period::ThisWeek, Period::ThisMonth, Period::ThisYear, Period::AllTime] {
+            if action_button(ui, period.label(), Vec2::new(80.0, 20.0), None).clicked() {
+                if period == Period::AllTime {
+                    state.from.clear();
+                    state.to.clear();
+                } else {
+                    let range = period.range(today);
+                    state.from = range.start.date().to_string();
+                    state.to = (range.end.date() - Days::new(1)).to_string();
+                }
+            }
+        }
+
+        let (text, style) =
+            if state.weekly { ("Per week", ButtonStyle::Success) } else { ("Per day", ButtonStyle::Neutral) };
+        if action_button(ui, text, Vec2::new(80.0, 20.0), Some(style)).on_hover_text("Toggle bar grouping").clicked() {
+            state.weekly = !state.weekly;
+        }
+    });
+}
+
+/// Draws playtime bars, cumulative time and session lengths of one game's sessions.
+pub fn draw_session_charts(ui: &mut Ui, state: &mut SessionChartsState, sessions: &[Session]) {
+    draw_range_selector(ui, state);
+
+    let (from, to) = selected_range(state, sessions);
+    let range = DateRange::days(from, to);
+    let in_range = sessions_in_range(sessions, &range);
+    if in_range.is_empty() {
+        ui.label(RichText::new("No sessions in this range").size(15.0));
+        return;
+    }
+
+    // --- Playtime per day or week ---
+    let (label, width, offset, periods) = if state.weekly {
+        ("Playtime per week", 6.0, 3.0, playtime_per_week(sessions, &range))
+    } else {
+        ("Playtime per day", 0.8, 0.5, playtime_per_day(sessions, &range))
+    };
+    let bars = periods
+        .into_iter()
+        .map(|(day, secs)| {
+            Bar::new(day.num_days_from_ce() as f64 + offset, secs as f64 / 3600.0).width(width).name(format!(
+                "{}: {}",
+                day,
+                format_duration(secs)
+            ))
+        })
+        .collect();
+
+    ui.label(RichText::new(label).size(14.0).weak());
+    chart("session_chart_playtime").x_axis_formatter(format_day_x).show(ui, |plot| {
+        let bars = BarChart::new(label, bars)
+            .color(ButtonStyle::Neutral.color())
+            .element_formatter(Box::new(|bar, _| bar.name.clone()));
+        plot.bar_chart(bars);
+    });
+
+    // --- Cumulative time ---
+    let cumulative = cumulative_playtime(sessions);
+    let before = cumulative.iter().take_while(|(end, _)| *end < range.start).last().map_or(0, |(_, total)| *total);
+    let mut points = vec![[day_x(range.start), before as f64 / 3600.0]];
+    points.extend(
+        cumulative
+            .iter()
+            .filter(|(end, _)| *end >= range.start && *end < range.end)
+            .map(|(end, total)| [day_x(*end), *total as f64 / 3600.0]),
+    );
+
+    ui.label(RichText::new("Total time").size(14.0).weak());
+    chart("session_chart_cumulative")
+        .x_axis_formatter(format_day_x)
+        .label_formatter(|_, point| {
+            let date = NaiveDate::from_num_days_from_ce_opt(point.x.floor() as i32).map(|d| d.to_string());
+            format!("{}\n{}", date.unwrap_or_default(), format_duration((point.y * 3600.0) as u64))
+        })
+        .show(ui, |plot| {
+            plot.line(Line::new("Total time", PlotPoints::from(points)).color(ButtonStyle::Success.color()));
+        });
+
+    // --- Session lengths ---
+    let bucket_hours = HISTOGRAM_BUCKET_SECS as f64 / 3600.0;
+    let bars = session_length_histogram(&in_range, HISTOGRAM_BUCKET_SECS)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, count)| *count > 0)
+        .map(|(i, count)| {
+            let from = i as u64 * HISTOGRAM_BUCKET_SECS;
+            Bar::new((i as f64 + 0.5) * bucket_hours, count as f64).width(bucket_hours * 0.9).name(format!(
+                "{} – {}: {} session(s)",
+                format_duration(from),
+                format_duration(from + HISTOGRAM_BUCKET_SECS),
+                count
+            ))
+        })
+        .collect();
+
+    ui.label(RichText::new("Session lengths").size(14.0).weak());
+    chart("session_chart_lengths")
+        .x_axis_formatter(|mark, _| format!("{}h", mark.value))
+        .y_axis_formatter(|mark, _| format!("{}", mark.value))
+        .show(ui, |plot| {
+            let bars = BarChart::new("Session lengths", bars)
+                .color(ButtonStyle::Neutral.color())
+                .element_formatter(Box::new(|bar, _| bar.name.clone()));
+            plot.bar_chart(bars);
+        });
+}
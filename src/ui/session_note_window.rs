@@ -0,0 +1,56 @@
+use crate::{core::json_storage::update_session_note, ui::app::TimeTrackerApp, ui::ui_patterns};
+use eframe::egui::{Color32, Context, FontId, RichText, TextEdit, Vec2, Window};
+
+/// Renders the post-session prompt for a note and progress marker.
+pub fn draw_session_note_window(app: &mut TimeTrackerApp, ctx: &Context) {
+    let Some(draft) = app.state.session_note_draft.as_mut() else {
+        return;
+    };
+
+    let game_name = app.state.games.iter().find(|g| g.id == draft.game_id).map(|g| g.name.clone()).unwrap_or_default();
+
+    let mut save_clicked = false;
+    let mut skip_clicked = false;
+
+    Window::new(format!("Session finished: {}", game_name)).collapsible(false).resizable(false).show(ctx, |ui| {
+        ui.label(RichText::new(format!("Played on {}", draft.date)).size(16.0));
+
+        ui.separator();
+
+        // --- Progress input field ---
+        ui_patterns::labeled_text_edit(ui, "Progress:", &mut draft.progress, 18.0, Vec2::new(220.0, 26.0), 16.0);
+
+        // --- Note input field ---
+        ui.label(RichText::new("Note:").size(18.0));
+        ui.add(
+            TextEdit::multiline(&mut draft.note)
+                .font(FontId::proportional(16.0))
+                .desired_rows(3)
+                .desired_width(310.0)
+                .background_color(Color32::from_rgb(200, 200, 200))
+                .text_color(Color32::from_rgb(40, 40, 40)),
+        );
+
+        ui.separator();
+
+        // --- Buttons ---
+        ui.horizontal(|ui| {
+            (save_clicked, skip_clicked) = ui_patterns::centered_two_buttons(
+                ui,
+                ("Save", "Skip"),
+                Vec2::new(70.0, 25.0),
+                20.0,
+                5.0,
+                (Some(ui_patterns::ButtonStyle::Success), None),
+            );
+        });
+    });
+
+    if save_clicked {
+        update_session_note(draft.session_id, draft.note.trim(), draft.progress.trim());
+    }
+
+    if save_clicked || skip_clicked {
+        app.state.session_note_draft = None;
+    }
+}
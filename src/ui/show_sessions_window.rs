@@ -1,22 +1,90 @@
-use crate::ui::ui_patterns::action_button;
+use crate::core::duration::format_date_time;
+use crate::core::json_storage::{Session, load_games};
+use crate::core::session_crud::{delete_session, merge_sessions, split_session};
+use crate::ui::app::SessionSplitDraft;
+use crate::ui::session_charts::draw_session_charts;
+use crate::ui::ui_patterns::{ButtonStyle, action_button, labeled_text_edit};
 use crate::{core::json_storage::load_sessions, ui::app::TimeTrackerApp};
-use eframe::egui::{self, Color32, RichText, ScrollArea, Vec2};
+use eframe::egui::{self, CollapsingHeader, Color32, RichText, ScrollArea, Vec2};
+
+/// Edit requested from a session row
+enum SessionAction {
+    Split,
+    MergeWithNext,
+    Delete,
+}
 
 /// Drow one session data
-fn draw_session_row(ui: &mut egui::Ui, date: &str, duration: &str) {
+fn draw_session_row(ui: &mut egui::Ui, session: &Session, has_next: bool) -> Option<SessionAction> {
     let text_color =
         if ui.visuals().dark_mode { Color32::from_rgb(200, 200, 200) } else { Color32::from_rgb(10, 10, 10) };
+    let mut action = None;
 
     ui.horizontal(|ui| {
-        ui.label(RichText::new(date).size(16.0));
+        ui.label(RichText::new(&session.date).size(16.0));
+        ui.add_space(8.0);
+        ui.label(RichText::new(&session.duration).size(16.0).color(text_color));
+        if !session.progress.is_empty() {
+            ui.add_space(8.0);
+            ui.label(RichText::new(format!("📍 {}", session.progress)).size(16.0).italics());
+        }
+
         ui.add_space(8.0);
-        ui.label(RichText::new(duration).size(16.0).color(text_color));
+        if action_button(ui, "✂", Vec2::new(25.0, 20.0), None).on_hover_text("Split session").clicked() {
+            action = Some(SessionAction::Split);
+        }
+        if has_next
+            && action_button(ui, "⤓", Vec2::new(25.0, 20.0), None).on_hover_text("Merge with next session").clicked()
+        {
+            action = Some(SessionAction::MergeWithNext);
+        }
+        if action_button(ui, "🗑", Vec2::new(25.0, 20.0), Some(ButtonStyle::Danger))
+            .on_hover_text("Delete session")
+            .clicked()
+        {
+            action = Some(SessionAction::Delete);
+        }
     });
+
+    if !session.note.is_empty() {
+        ui.indent(session.id, |ui| {
+            ui.label(RichText::new(&session.note).size(14.0).color(text_color));
+        });
+    }
+
+    action
+}
+
+/// Draws the split time input. Returns `(confirm_clicked, cancel_clicked)`.
+fn draw_split_input(ui: &mut egui::Ui, draft: &mut SessionSplitDraft) -> (bool, bool) {
+    ui.indent(("split", draft.session_id), |ui| {
+        ui.horizontal(|ui| {
+            labeled_text_edit(ui, "Split at:", &mut draft.at, 16.0, Vec2::new(170.0, 22.0), 16.0);
+            let confirm = action_button(ui, "Split", Vec2::new(50.0, 22.0), Some(ButtonStyle::Success)).clicked();
+            let cancel = action_button(ui, "Cancel", Vec2::new(50.0, 22.0), None).clicked();
+            (confirm, cancel)
+        })
+        .inner
+    })
+    .inner
+}
+
+/// Checks whether the session note or progress contains the search query (case-insensitive),
+/// or the session started or ended at a date starting with it, e.g. `"2025-01-31"`.
+fn matches_search(session: &Session, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    let started = session.start_time().map(format_date_time).unwrap_or_default();
+    query.is_empty()
+        || session.note.to_lowercase().contains(&query)
+        || session.progress.to_lowercase().contains(&query)
+        || session.date.starts_with(&query)
+        || started.starts_with(&query)
 }
 
 /// Drow sessions list for selected game
 pub fn draw_sessions_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
     let mut window_open = app.state.show_sessions_window.is_some();
+    let mut sessions_changed = false;
 
     if let Some(game_id) = app.state.show_sessions_window {
         if let Some(game) = app.state.games.iter().find(|g| g.id == game_id) {
@@ -25,8 +93,29 @@ pub fn draw_sessions_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
                 .default_size(Vec2::new(300.0, 200.0))
                 .open(&mut window_open)
                 .show(ctx, |ui| {
+                    // --- Notes search ---
+                    labeled_text_edit(ui, "Search:", &mut app.state.session_search, 16.0, Vec2::new(200.0, 24.0), 16.0);
+
+                    if let Some(error) = &app.state.session_edit_error {
+                        ui.label(RichText::new(error).size(14.0).color(ButtonStyle::Danger.color()));
+                    }
+
+                    ui.separator();
+
+                    let mut sessions = load_sessions(game.id);
+                    sessions.sort_by_key(|s| s.start_time());
+
+                    // --- Charts ---
+                    CollapsingHeader::new(RichText::new("📈 Charts").size(16.0)).id_salt("session_charts").show(
+                        ui,
+                        |ui| {
+                            draw_session_charts(ui, &mut app.state.session_charts, &sessions);
+                        },
+                    );
+
+                    ui.separator();
+
                     // --- Sessions list ---
-                    let sessions = load_sessions(game.id);
                     let footer_height = 44.0; // for button
                     let scroll_height = (ui.available_height() - footer_height).max(0.0);
 
@@ -35,9 +124,47 @@ pub fn draw_sessions_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
 
                         if sessions.is_empty() {
                             ui.label(RichText::new("Sessions list is empty").size(18.0));
-                        } else {
-                            for s in &sessions {
-                                draw_session_row(ui, &s.date, &s.duration);
+                        }
+
+                        for (i, s) in sessions.iter().enumerate() {
+                            if !matches_search(s, &app.state.session_search) {
+                                continue;
+                            }
+
+                            // Merge only with the row shown right below, and only if no hidden session lies between
+                            let next =
+                                sessions.get(i + 1).filter(|next| matches_search(next, &app.state.session_search));
+                            let result = match draw_session_row(ui, s, next.is_some()) {
+                                Some(SessionAction::Split) => {
+                                    let at = s.start_time().map(format_date_time).unwrap_or_default();
+                                    app.state.session_split_draft = Some(SessionSplitDraft { session_id: s.id, at });
+                                    Ok(())
+                                }
+                                Some(SessionAction::MergeWithNext) => {
+                                    next.map_or(Ok(()), |next| merge_sessions(s, next)).map(|_| sessions_changed = true)
+                                }
+                                Some(SessionAction::Delete) => delete_session(s).map(|_| sessions_changed = true),
+                                None => Ok(()),
+                            };
+                            if let Err(error) = result {
+                                app.state.session_edit_error = Some(error);
+                            }
+
+                            if let Some(draft) = app.state.session_split_draft.as_mut()
+                                && draft.session_id == s.id
+                            {
+                                let (confirm, cancel) = draw_split_input(ui, draft);
+                                if confirm {
+                                    match split_session(s, &draft.at) {
+                                        Ok(()) => {
+                                            sessions_changed = true;
+                                            app.state.session_split_draft = None;
+                                        }
+                                        Err(error) => app.state.session_edit_error = Some(error),
+                                    }
+                                } else if cancel {
+                                    app.state.session_split_draft = None;
+                                }
                             }
                         }
                     });
@@ -52,7 +179,15 @@ pub fn draw_sessions_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
         }
     }
 
-    if !window_open {
+    if sessions_changed {
+        app.state.session_edit_error = None;
+        app.state.games = load_games();
+        app.goal_header = None;
+    }
+
+    if !window_open || app.state.show_sessions_window.is_none() {
         app.state.show_sessions_window = None;
+        app.state.session_split_draft = None;
+        app.state.session_edit_error = None;
     }
 }
@@ -0,0 +1,190 @@
+use crate::core::duration::format_duration;
+use crate::core::json_storage::{Session, load_all_sessions};
+use crate::core::statistics::{Period, RangeStatistics, TimeDistribution, range_statistics, time_distribution};
+use crate::ui::app::TimeTrackerApp;
+use crate::ui::goals_window::{describe_goal, draw_goal_progress};
+use crate::ui::ui_patterns::ButtonStyle;
+use chrono::Local;
+use eframe::egui::{
+    self, Align2, Color32, FontId, Grid, Pos2, ProgressBar, Rect, RichText, ScrollArea, Sense, Ui, Vec2,
+};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Draws the totals of every period. Clicking a row selects it for the details below.
+fn draw_period_totals(ui: &mut Ui, app: &mut TimeTrackerApp, sessions: &[Session]) {
+    let today = Local::now().date_naive();
+
+    Grid::new("statistics_periods").num_columns(3).striped(true).spacing([20.0, 4.0]).show(ui, |ui| {
+        for period in Period::ALL {
+            let stats = range_statistics(sessions, &period.range(today));
+            let selected = app.state.statistics_period == period;
+            if ui.selectable_label(selected, RichText::new(period.label()).size(16.0)).clicked() {
+                app.state.statistics_period = period;
+            }
+            ui.label(RichText::new(format_duration(stats.total_secs)).size(16.0).strong());
+            ui.label(RichText::new(format!("{} session(s)", stats.session_count)).size(14.0));
+            ui.end_row();
+        }
+    });
+}
+
+/// Draws session numbers and the per-game share of the selected period.
+fn draw_period_details(ui: &mut Ui, app: &TimeTrackerApp, stats: &RangeStatistics) {
+    Grid::new("statistics_details").num_columns(2).spacing([10.0, 4.0]).show(ui, |ui| {
+        for (label, value) in [
+            ("Total time:", format_duration(stats.total_secs)),
+            ("Sessions:", stats.session_count.to_string()),
+            ("Average session:", format_duration(stats.average_secs)),
+            ("Longest session:", format_duration(stats.longest_secs)),
+            ("Active days:", stats.active_days.to_string()),
+        ] {
+            ui.label(RichText::new(label).size(15.0));
+            ui.label(RichText::new(value).size(15.0));
+            ui.end_row();
+        }
+    });
+
+    if stats.per_game.is_empty() {
+        return;
+    }
+
+    ui.add_space(6.0);
+    ui.label(RichText::new("Per game").size(18.0).strong());
+
+    Grid::new("statistics_per_game").num_columns(3).spacing([10.0, 4.0]).show(ui, |ui| {
+        for (game_id, secs) in &stats.per_game {
+            let name = app.state.games.iter().find(|g| g.id == *game_id).map_or("Unknown game", |g| g.name.as_str());
+            let share = *secs as f32 / stats.total_secs.max(1) as f32;
+
+            ui.label(RichText::new(name).size(15.0));
+            ui.add(ProgressBar::new(share).desired_width(160.0).text(format!("{:.0}%", share * 100.0)));
+            ui.label(RichText::new(format_duration(*secs)).size(15.0));
+            ui.end_row();
+        }
+    });
+}
+
+/// Paints the weekday × hour matrix, darker cells for more playtime.
+fn draw_time_distribution(ui: &mut Ui, distribution: &TimeDistribution) {
+    const CELL: Vec2 = Vec2::new(14.0, 14.0);
+    const LABEL_WIDTH: f32 = 34.0;
+
+    let max = distribution.matrix.iter().flatten().copied().max().unwrap_or(0).max(1);
+    let size = Vec2::new(LABEL_WIDTH + 24.0 * CELL.x, 8.0 * CELL.y);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+    let painter = ui.painter_at(rect);
+    let text_color = ui.visuals().weak_text_color();
+    let empty = if ui.visuals().dark_mode { Color32::from_gray(50) } else { Color32::from_gray(225) };
+    let font = FontId::proportional(11.0);
+
+    for hour in (0..24).step_by(3) {
+        let pos = rect.min + Vec2::new(LABEL_WIDTH + hour as f32 * CELL.x, 0.0);
+        painter.text(pos, Align2::LEFT_TOP, hour.to_string(), font.clone(), text_color);
+    }
+
+    let mut hovered = None;
+    for (weekday, hours) in distribution.matrix.iter().enumerate() {
+        let top = rect.top() + (weekday as f32 + 1.0) * CELL.y;
+        painter.text(Pos2::new(rect.left(), top), Align2::LEFT_TOP, WEEKDAYS[weekday], font.clone(), text_color);
+
+        for (hour, secs) in hours.iter().enumerate() {
+            let min = Pos2::new(rect.left() + LABEL_WIDTH + hour as f32 * CELL.x, top);
+            let cell = Rect::from_min_size(min, CELL - Vec2::splat(2.0));
+            let color = if *secs == 0 {
+                empty
+            } else {
+                empty.lerp_to_gamma(ButtonStyle::Success.color(), 0.2 + 0.8 * (*secs as f32 / max as f32))
+            };
+            painter.rect_filled(cell, 2.0, color);
+            if response.hover_pos().is_some_and(|pos| cell.expand(1.0).contains(pos)) {
+                hovered = Some((weekday, hour, *secs));
+            }
+        }
+    }
+
+    if let Some((weekday, hour, secs)) = hovered {
+        response.on_hover_text(format!(
+            "{} {:02}:00–{:02}:00: {}",
+            WEEKDAYS[weekday],
+            hour,
+            hour + 1,
+            format_duration(secs)
+        ));
+    }
+}
+
+/// Draws the total and average playtime of each weekday.
+fn draw_weekday_averages(ui: &mut Ui, distribution: &TimeDistribution) {
+    let max = distribution.weekday_averages.iter().copied().max().unwrap_or(0).max(1);
+
+    Grid::new("statistics_weekdays").num_columns(3).spacing([10.0, 2.0]).show(ui, |ui| {
+        for (weekday, name) in WEEKDAYS.iter().enumerate() {
+            let average = distribution.weekday_averages[weekday];
+            ui.label(RichText::new(*name).size(14.0));
+            ui.add(
+                ProgressBar::new(average as f32 / max as f32)
+                    .desired_width(160.0)
+                    .text(format!("⌀ {}", format_duration(average))),
+            );
+            ui.label(RichText::new(format_duration(distribution.weekday_totals[weekday])).size(14.0).weak());
+            ui.end_row();
+        }
+    });
+}
+
+/// Renders the playtime statistics dashboard of the whole library.
+pub fn draw_statistics_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_statistics_window {
+        return;
+    }
+
+    let sessions = load_all_sessions();
+    let mut window_open = true;
+
+    egui::Window::new("Statistics").resizable(true).default_size(Vec2::new(460.0, 480.0)).open(&mut window_open).show(
+        ctx,
+        |ui| {
+            ScrollArea::vertical().show(ui, |ui| {
+                if sessions.is_empty() {
+                    ui.label(RichText::new("No sessions yet").size(16.0));
+                    return;
+                }
+
+                draw_period_totals(ui, app, &sessions);
+
+                ui.separator();
+
+                // --- Goals ---
+                if !app.state.goals.is_empty() {
+                    ui.label(RichText::new("Goals").size(18.0).strong());
+                    for goal in &app.state.goals {
+                        ui.label(RichText::new(describe_goal(goal, &app.state.games)).size(15.0));
+                        draw_goal_progress(ui, goal, &sessions);
+                    }
+                    ui.separator();
+                }
+
+                let period = app.state.statistics_period;
+                ui.label(RichText::new(period.label()).size(18.0).strong());
+                let range = period.range(Local::now().date_naive());
+                let stats = range_statistics(&sessions, &range);
+                draw_period_details(ui, app, &stats);
+
+                ui.separator();
+
+                // --- When the sessions happen ---
+                ui.label(RichText::new("Time of day").size(18.0).strong());
+                let distribution = time_distribution(&sessions, &range);
+                draw_time_distribution(ui, &distribution);
+                ui.add_space(6.0);
+                ui.label(RichText::new("Average per weekday").size(14.0).weak());
+                draw_weekday_averages(ui, &distribution);
+            });
+        },
+    );
+
+    if !window_open {
+        app.state.show_statistics_window = false;
+    }
+}
@@ -0,0 +1,76 @@
+use crate::core::json_storage::load_games;
+use crate::core::relink::find_missing_games;
+use crate::core::sync_storage::{LOGS_DIR, device_summary, enable_sync_storage, is_enabled, pick_up_sync_files};
+use crate::ui::app::TimeTrackerApp;
+use crate::ui::ui_patterns::{ButtonStyle, action_button};
+use eframe::egui::{self, Grid, RichText, Vec2};
+
+/// Renders the window for keeping the data folder in Syncthing, Nextcloud and similar tools.
+pub fn draw_sync_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_sync_window {
+        return;
+    }
+
+    let mut window_open = true;
+    let mut enable_clicked = false;
+    let mut check_clicked = false;
+    let enabled = is_enabled();
+
+    egui::Window::new("Folder sync").resizable(false).open(&mut window_open).show(ctx, |ui| {
+        if enabled {
+            ui.label(RichText::new(format!("Sessions are kept in one log per device in {}/", LOGS_DIR)).size(15.0));
+            Grid::new("sync_devices").num_columns(2).spacing([14.0, 4.0]).show(ui, |ui| {
+                for (device, changes, own) in device_summary() {
+                    let name = if own { format!("{} (this device)", device) } else { device };
+                    ui.label(RichText::new(name).size(14.0).monospace());
+                    ui.label(RichText::new(format!("{} changes", changes)).size(14.0));
+                    ui.end_row();
+                }
+            });
+        } else {
+            ui.label(RichText::new("Sessions are kept in sessions.json").size(15.0));
+            ui.label(
+                RichText::new(
+                    "If the data folder is synced between devices, switch to device logs on one of them. \
+                     The others follow once the folder has synced.",
+                )
+                .size(13.0)
+                .weak(),
+            );
+            enable_clicked =
+                action_button(ui, "Switch to device logs", Vec2::new(170.0, 26.0), Some(ButtonStyle::Success))
+                    .clicked();
+        }
+
+        ui.separator();
+        check_clicked = action_button(ui, "🔎 Merge conflict copies", Vec2::new(170.0, 26.0), None)
+            .on_hover_text("Picks up *.sync-conflict* files of games and sessions")
+            .clicked();
+
+        if let Some(message) = &app.state.sync_message {
+            ui.separator();
+            ui.label(RichText::new(message).size(14.0));
+        }
+    });
+
+    if enable_clicked {
+        app.state.sync_message = Some(match enable_sync_storage() {
+            Ok(moved) => format!("Moved {} sessions to the log of this device", moved),
+            Err(err) => err,
+        });
+    }
+    if check_clicked {
+        app.state.sync_message = Some(match pick_up_sync_files() {
+            Ok(report) => report.describe(),
+            Err(err) => err,
+        });
+        app.state.games = load_games();
+        app.missing_games = find_missing_games(&app.state.games);
+        app.goal_header = None;
+    }
+
+    if !window_open {
+        app.state.show_sync_window = false;
+        app.state.sync_message = None;
+    }
+}
@@ -0,0 +1,128 @@
+use crate::core::json_storage::load_games;
+use crate::core::tag_crud::{all_tags, delete_tag, merge_tags, rename_tag};
+use crate::ui::app::TimeTrackerApp;
+use crate::ui::ui_patterns::{ButtonStyle, action_button, labeled_text_edit, tag_chip};
+use eframe::egui::{self, ComboBox, RichText, ScrollArea, Vec2};
+
+/// Tag change requested in the window
+enum TagEdit {
+    Rename(String, String),
+    Merge(Vec<String>, String),
+    Delete(String),
+}
+
+/// Renders the window for renaming, merging and deleting tags across all games.
+pub fn draw_tags_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_tags_window {
+        return;
+    }
+
+    let tags = all_tags(&app.state.games);
+    let mut window_open = true;
+    let mut edit = None;
+
+    egui::Window::new("Tags").resizable(true).default_size(Vec2::new(360.0, 280.0)).open(&mut window_open).show(
+        ctx,
+        |ui| {
+            ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                ui.set_min_width(ui.available_width());
+
+                if tags.is_empty() {
+                    ui.label(RichText::new("No tags yet, add them in game details").size(16.0));
+                }
+
+                for (tag, count) in &tags {
+                    ui.horizontal(|ui| {
+                        let selected = app.state.tag_edit_selection.contains(tag);
+                        if tag_chip(ui, tag, selected).clicked() {
+                            if selected {
+                                app.state.tag_edit_selection.retain(|t| t != tag);
+                            } else {
+                                app.state.tag_edit_selection.push(tag.clone());
+                            }
+                        }
+                        ui.label(RichText::new(format!("{} game(s)", count)).size(14.0));
+
+                        if action_button(ui, "🗑", Vec2::new(24.0, 20.0), Some(ButtonStyle::Danger))
+                            .on_hover_text("Remove tag from all games")
+                            .clicked()
+                        {
+                            edit = Some(TagEdit::Delete(tag.clone()));
+                        }
+                    });
+                }
+            });
+
+            ui.separator();
+
+            // --- Rename or merge selected tags ---
+            let selection = &app.state.tag_edit_selection;
+            ui.label(RichText::new(format!("Selected: {}", selection.join(", "))).size(14.0));
+
+            ui.horizontal(|ui| {
+                labeled_text_edit(ui, "New name:", &mut app.state.tag_edit_target, 16.0, Vec2::new(150.0, 24.0), 16.0);
+
+                ComboBox::from_id_salt("merge_into_tag").selected_text("Pick…").show_ui(ui, |ui| {
+                    for (tag, _) in &tags {
+                        if ui.selectable_label(false, tag).clicked() {
+                            app.state.tag_edit_target = tag.clone();
+                        }
+                    }
+                });
+            });
+
+            let target = app.state.tag_edit_target.trim().to_string();
+            let selection = &app.state.tag_edit_selection;
+            ui.horizontal(|ui| {
+                let size = Vec2::new(80.0, 24.0);
+                if action_button(ui, "Rename", size, Some(ButtonStyle::Success)).clicked()
+                    && selection.len() == 1
+                    && !target.is_empty()
+                {
+                    edit = Some(TagEdit::Rename(selection[0].clone(), target.clone()));
+                }
+                if action_button(ui, "Merge", size, Some(ButtonStyle::Neutral)).clicked()
+                    && !selection.is_empty()
+                    && !target.is_empty()
+                {
+                    edit = Some(TagEdit::Merge(selection.clone(), target.clone()));
+                }
+            });
+        },
+    );
+
+    if let Some(edit) = edit {
+        match edit {
+            TagEdit::Rename(old, new) => {
+                rename_tag(&old, &new);
+                replace_in_filter(&mut app.state.tag_filter, &[old], &new);
+            }
+            TagEdit::Merge(sources, target) => {
+                merge_tags(&sources, &target);
+                replace_in_filter(&mut app.state.tag_filter, &sources, &target);
+            }
+            TagEdit::Delete(tag) => {
+                delete_tag(&tag);
+                app.state.tag_filter.retain(|t| *t != tag);
+            }
+        }
+        app.state.tag_edit_selection.clear();
+        app.state.tag_edit_target.clear();
+        app.state.games = load_games();
+    }
+
+    if !window_open {
+        app.state.show_tags_window = false;
+        app.state.tag_edit_selection.clear();
+        app.state.tag_edit_target.clear();
+    }
+}
+
+/// Keeps the main table tag filter pointing at the renamed or merged tag.
+fn replace_in_filter(filter: &mut Vec<String>, sources: &[String], target: &str) {
+    let had_source = filter.iter().any(|t| sources.contains(t));
+    filter.retain(|t| !sources.contains(t));
+    if had_source && !filter.iter().any(|t| t == target) {
+        filter.push(target.to_string());
+    }
+}
@@ -1,4 +1,6 @@
-use eframe::egui::{Button, Color32, Direction, FontId, Layout, RichText, TextEdit, Ui, Vec2};
+use eframe::egui::{
+    Button, Color32, Direction, FontId, Label, Layout, Pos2, Rect, RichText, Sense, TextEdit, Ui, Vec2,
+};
 use egui_extras::TableRow;
 
 /// Common button styles mapped to colors
@@ -29,6 +31,12 @@ pub fn action_button(ui: &mut Ui, text: &str, size: Vec2, style: Option<ButtonSt
     ui.add(button)
 }
 
+/// Draw a small rounded tag label, highlighted when `selected`.
+pub fn tag_chip(ui: &mut Ui, text: &str, selected: bool) -> eframe::egui::Response {
+    let fill = if selected { ButtonStyle::Success.color() } else { ButtonStyle::Neutral.color() };
+    ui.add(Button::new(RichText::new(text).size(13.0).color(Color32::WHITE)).fill(fill).corner_radius(8.0).small())
+}
+
 /// Draw a centered button inside a table cell.
 pub fn centered_button(ui: &mut Ui, text: &str, size: Vec2, style: Option<ButtonStyle>) -> bool {
     let mut clicked = false;
@@ -49,6 +57,18 @@ pub fn header_cell(row: &mut TableRow<'_, '_>, text: &str) {
     });
 }
 
+/// Draw a centered header cell that reacts to clicks.
+/// Returns `true` if the header was clicked.
+pub fn sortable_header_cell(row: &mut TableRow<'_, '_>, text: &str) -> bool {
+    let mut clicked = false;
+    row.col(|ui| {
+        ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+            clicked = ui.add(Label::new(RichText::new(text).size(20.0).strong()).sense(Sense::click())).clicked();
+        });
+    });
+    clicked
+}
+
 /// Draw two centered buttons with given spacing.
 /// Returns a tuple of (first_clicked, second_clicked).
 pub fn centered_two_buttons(
@@ -95,3 +115,33 @@ pub fn labeled_text_edit(
         ui.add_sized(edit_size, edit);
     });
 }
+
+/// Draws a simple bar chart filling the available width.
+/// Hovering a bar shows its label and value formatted with `format_value`.
+pub fn bar_chart(ui: &mut Ui, bars: &[(String, u64)], height: f32, format_value: impl Fn(u64) -> String) {
+    let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), height), Sense::hover());
+    let max = bars.iter().map(|(_, v)| *v).max().unwrap_or(0).max(1);
+    if bars.is_empty() {
+        return;
+    }
+
+    let painter = ui.painter_at(rect);
+    let slot = rect.width() / bars.len() as f32;
+    let hovered = response.hover_pos().map(|pos| (((pos.x - rect.left()) / slot) as usize).min(bars.len() - 1));
+
+    for (i, (_, value)) in bars.iter().enumerate() {
+        let bar_height = rect.height() * (*value as f32 / max as f32);
+        let left = rect.left() + slot * i as f32 + slot * 0.1;
+        let bar = Rect::from_min_max(
+            Pos2::new(left, rect.bottom() - bar_height),
+            Pos2::new(left + slot * 0.8, rect.bottom()),
+        );
+        let color = if hovered == Some(i) { ButtonStyle::Success.color() } else { ButtonStyle::Neutral.color() };
+        painter.rect_filled(bar, 2.0, color);
+    }
+
+    if let Some(i) = hovered {
+        let (label, value) = &bars[i];
+        response.on_hover_text(format!("{}: {}", label, format_value(*value)));
+    }
+}
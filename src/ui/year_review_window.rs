@@ -0,0 +1,148 @@
+use crate::core::duration::format_duration;
+use crate::core::json_storage::load_all_sessions;
+use crate::core::year_review::{YearReview, change_percent, month_name, review_to_html, year_review};
+use crate::ui::app::TimeTrackerApp;
+use crate::ui::ui_patterns::{ButtonStyle, action_button, bar_chart};
+use chrono::{Datelike, Local};
+use eframe::egui::{self, Grid, ProgressBar, RichText, ScrollArea, Ui, Vec2};
+use rfd::FileDialog;
+use std::fs;
+
+/// Draws the headline numbers with their change against the previous year.
+fn draw_numbers(ui: &mut Ui, review: &YearReview) {
+    let compare = |current: u64, previous: u64| change_percent(current, previous).unwrap_or_default();
+
+    Grid::new("year_review_numbers").num_columns(3).spacing([14.0, 4.0]).show(ui, |ui| {
+        for (label, value, change) in [
+            ("Total time:", format_duration(review.total_secs), compare(review.total_secs, review.previous_total_secs)),
+            (
+                "Sessions:",
+                review.session_count.to_string(),
+                compare(review.session_count as u64, review.previous_session_count as u64),
+            ),
+            (
+                "Active days:",
+                review.active_days.to_string(),
+                compare(review.active_days as u64, review.previous_active_days as u64),
+            ),
+            ("Games started:", review.games_started.to_string(), String::new()),
+            ("Games finished:", review.games_finished.to_string(), String::new()),
+        ] {
+            ui.label(RichText::new(label).size(16.0));
+            ui.label(RichText::new(value).size(16.0).strong());
+            ui.label(RichText::new(change).size(14.0).weak())
+                .on_hover_text(format!("Compared with {}", review.year - 1));
+            ui.end_row();
+        }
+
+        if let Some(longest) = &review.longest_session {
+            ui.label(RichText::new("Longest session:").size(16.0));
+            ui.label(RichText::new(format_duration(longest.secs)).size(16.0).strong());
+            ui.label(RichText::new(format!("{}, {}", longest.game_name, longest.date)).size(14.0).weak());
+            ui.end_row();
+        }
+        if let Some((day, secs)) = review.busiest_day {
+            ui.label(RichText::new("Busiest day:").size(16.0));
+            ui.label(RichText::new(day.to_string()).size(16.0).strong());
+            ui.label(RichText::new(format_duration(secs)).size(14.0).weak());
+            ui.end_row();
+        }
+        if let Some(month) = review.busiest_month() {
+            ui.label(RichText::new("Busiest month:").size(16.0));
+            ui.label(RichText::new(month_name(month)).size(16.0).strong());
+            ui.label(RichText::new(format_duration(review.monthly_secs[month])).size(14.0).weak());
+            ui.end_row();
+        }
+    });
+}
+
+/// Asks for a file and writes the review as HTML. Returns the message to show.
+fn export_html(review: &YearReview) -> Option<String> {
+    let path = FileDialog::new()
+        .set_file_name(format!("{}-in-review.html", review.year))
+        .add_filter("HTML", &["html"])
+        .save_file()?;
+
+    Some(match fs::write(&path, review_to_html(review)) {
+        Ok(()) => format!("Saved to {}", path.display()),
+        Err(err) => format!("Can't save report: {}", err),
+    })
+}
+
+/// Renders the year-in-review summary with HTML export.
+pub fn draw_year_review_window(app: &mut TimeTrackerApp, ctx: &egui::Context) {
+    if !app.state.show_year_review_window {
+        return;
+    }
+
+    let year = app.state.year_review_year.unwrap_or_else(|| Local::now().year());
+    let review = year_review(&app.state.games, &load_all_sessions(), year);
+    let mut window_open = true;
+
+    egui::Window::new("Year in review")
+        .resizable(true)
+        .default_size(Vec2::new(460.0, 520.0))
+        .open(&mut window_open)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if action_button(ui, "⏴", Vec2::new(24.0, 24.0), None).clicked() {
+                    app.state.year_review_year = Some(year - 1);
+                }
+                ui.label(RichText::new(year.to_string()).size(20.0).strong());
+                if action_button(ui, "⏵", Vec2::new(24.0, 24.0), None).clicked() {
+                    app.state.year_review_year = Some(year + 1);
+                }
+
+                ui.add_space(20.0);
+                if action_button(ui, "💾 Export HTML…", Vec2::new(130.0, 24.0), Some(ButtonStyle::Success)).clicked()
+                    && let Some(message) = export_html(&review)
+                {
+                    app.state.year_review_message = Some(message);
+                }
+            });
+            if let Some(message) = &app.state.year_review_message {
+                ui.label(RichText::new(message).size(14.0));
+            }
+
+            ui.separator();
+
+            ScrollArea::vertical().show(ui, |ui| {
+                if review.session_count == 0 {
+                    ui.label(RichText::new("Nothing played this year").size(16.0));
+                    return;
+                }
+
+                draw_numbers(ui, &review);
+
+                ui.add_space(8.0);
+                ui.label(RichText::new("Playtime per month").size(18.0).strong());
+                let months: Vec<(String, u64)> = review
+                    .monthly_secs
+                    .iter()
+                    .enumerate()
+                    .map(|(m, secs)| (month_name(m).to_string(), *secs))
+                    .collect();
+                bar_chart(ui, &months, 80.0, format_duration);
+
+                ui.add_space(8.0);
+                ui.label(RichText::new("Top games").size(18.0).strong());
+                let max = review.top_games.first().map_or(1, |(_, secs)| *secs).max(1);
+                Grid::new("year_review_top").num_columns(2).spacing([10.0, 4.0]).show(ui, |ui| {
+                    for (name, secs) in &review.top_games {
+                        ui.label(RichText::new(name).size(15.0));
+                        ui.add(
+                            ProgressBar::new(*secs as f32 / max as f32)
+                                .desired_width(200.0)
+                                .text(format_duration(*secs)),
+                        );
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+
+    if !window_open {
+        app.state.show_year_review_window = false;
+        app.state.year_review_message = None;
+    }
+}